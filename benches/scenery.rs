@@ -0,0 +1,109 @@
+// Coût par frame de la sélection du décor visible pour un monde de 100 000
+// éléments : `cargo bench --no-default-features --bench scenery`
+use std::time::{Duration, Instant};
+
+use vibegame::camera::Camera;
+use vibegame::color::Color;
+use vibegame::config::CameraConfig;
+use vibegame::drawable::Drawable;
+use vibegame::math::Vector2;
+use vibegame::render_queue::RenderQueue;
+use vibegame::renderer::Renderer;
+use vibegame::scenery::{parallax_factor, Scenery, SceneryElement};
+
+const ELEMENT_COUNT: usize = 100_000;
+const ELEMENT_SPACING: f32 = 25.0;
+const FRAMES: u32 = 2_000;
+const VIEW_WIDTH: f32 = 800.0;
+const VIEW_HEIGHT: f32 = 600.0;
+
+// Renderer qui ne dessine rien : seul le parcours du décor est mesuré
+struct NullRenderer;
+
+impl Renderer for NullRenderer {
+    fn clear_background(&mut self, _: Color) {}
+    fn draw_rectangle(&mut self, _: i32, _: i32, _: i32, _: i32, _: Color) {}
+    fn draw_rectangle_lines(&mut self, _: i32, _: i32, _: i32, _: i32, _: Color) {}
+    fn draw_triangle(&mut self, _: Vector2, _: Vector2, _: Vector2, _: Color) {}
+    fn draw_circle(&mut self, _: i32, _: i32, _: f32, _: Color) {}
+    fn draw_line(&mut self, _: i32, _: i32, _: i32, _: i32, _: Color) {}
+    fn draw_text(&mut self, _: &str, _: i32, _: i32, _: i32, _: Color) {}
+    fn measure_text(&self, _: &str, _: i32) -> i32 {
+        0
+    }
+}
+
+fn element(index: usize) -> Box<dyn Drawable> {
+    let layer = (index % 4) as i32;
+    let width = 20.0 + (index % 7) as f32 * 40.0;
+    Box::new(SceneryElement::new(
+        index as f32 * ELEMENT_SPACING,
+        300.0,
+        width,
+        50.0,
+        Color::GREEN,
+        layer,
+    ))
+}
+
+// Caméra qui parcourt tout le monde au fil des frames
+fn camera_at(frame: u32) -> Camera {
+    let world_width = ELEMENT_COUNT as f32 * ELEMENT_SPACING;
+    let x = world_width * frame as f32 / FRAMES as f32;
+    Camera::new(
+        CameraConfig::default(),
+        VIEW_WIDTH,
+        VIEW_HEIGHT,
+        Vector2::new(x, 0.0),
+    )
+}
+
+fn report(name: &str, elapsed: Duration, drawn: usize) {
+    println!(
+        "{:<28} {:>10.2} µs/frame  ({} éléments dessinés en moyenne)",
+        name,
+        elapsed.as_secs_f64() * 1e6 / FRAMES as f64,
+        drawn / FRAMES as usize
+    );
+}
+
+fn main() {
+    let mut scenery = Scenery::new();
+    for index in 0..ELEMENT_COUNT {
+        scenery.push(element(index));
+    }
+    println!("{} éléments de décor", scenery.get_element_count());
+
+    // Décor indexé par couche et par tronçon
+    let mut drawn = 0;
+    let start = Instant::now();
+    for frame in 0..FRAMES {
+        let camera = camera_at(frame);
+        let mut queue = RenderQueue::new();
+        scenery.queue_draw(&mut queue, &camera);
+        drawn += queue.len();
+        queue.draw(&mut NullRenderer, VIEW_WIDTH);
+    }
+    report("index par tronçons", start.elapsed(), drawn);
+
+    // Référence : parcours de tous les éléments à chaque frame
+    let elements: Vec<Box<dyn Drawable>> = (0..ELEMENT_COUNT).map(element).collect();
+    let mut drawn = 0;
+    let start = Instant::now();
+    for frame in 0..FRAMES {
+        let camera = camera_at(frame);
+        let mut queue = RenderQueue::new();
+        for element in &elements {
+            let (x, y) = element.get_position();
+            let (width, _) = element.get_size();
+            let factor = parallax_factor(element.get_layer());
+            let screen = camera.world_to_screen_parallax(Vector2::new(x, y), factor);
+            if camera.is_visible(screen.x, width) {
+                queue.push(element.as_ref(), screen);
+            }
+        }
+        drawn += queue.len();
+        queue.draw(&mut NullRenderer, VIEW_WIDTH);
+    }
+    report("parcours complet", start.elapsed(), drawn);
+}
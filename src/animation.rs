@@ -0,0 +1,312 @@
+use crate::sprite::SpriteAnimation;
+
+// États d'animation du joueur
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnimationState {
+    Idle,
+    RunLeft,
+    RunRight,
+    Charging, // Charge du saut, au sol
+    Rising,   // En l'air, en montée
+    Falling,  // En l'air, en descente
+    Landing,  // Réception après un saut, jouée une fois
+    Dying,    // Après un choc fatal, jouée une fois puis figée
+}
+
+impl AnimationState {
+    // Nom de l'animation dans les feuilles de sprites (`sprites.toml`)
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            AnimationState::Idle => "idle",
+            AnimationState::RunLeft => "run_left",
+            AnimationState::RunRight => "run_right",
+            AnimationState::Charging => "charging",
+            AnimationState::Rising => "rising",
+            AnimationState::Falling => "falling",
+            AnimationState::Landing => "landing",
+            AnimationState::Dying => "dying",
+        }
+    }
+
+    // Nombre d'images du dessin procédural
+    pub fn get_frame_count(&self) -> usize {
+        match self {
+            AnimationState::Idle => 4,
+            AnimationState::RunLeft | AnimationState::RunRight => 4,
+            AnimationState::Charging => 2,
+            AnimationState::Rising | AnimationState::Falling => 1,
+            AnimationState::Landing => 3,
+            AnimationState::Dying => 4,
+        }
+    }
+
+    // Durée d'affichage d'une image (secondes)
+    pub fn get_frame_duration(&self) -> f32 {
+        match self {
+            AnimationState::Idle => 0.25,
+            AnimationState::RunLeft | AnimationState::RunRight => 0.1,
+            AnimationState::Charging => 0.08,
+            AnimationState::Rising | AnimationState::Falling => 0.1,
+            AnimationState::Landing => 0.06,
+            AnimationState::Dying => 0.15,
+        }
+    }
+
+    // Les animations jouées une fois s'arrêtent sur leur dernière image
+    pub fn is_looping(&self) -> bool {
+        !matches!(self, AnimationState::Landing | AnimationState::Dying)
+    }
+
+    fn is_airborne(&self) -> bool {
+        matches!(self, AnimationState::Rising | AnimationState::Falling)
+    }
+}
+
+// Index de l'image affichée `time` secondes après le début d'une animation
+// de `frame_count` images
+pub fn frame_at(time: f32, frame_duration: f32, frame_count: usize, looping: bool) -> usize {
+    let frame = (time.max(0.0) / frame_duration) as usize;
+    if looping {
+        frame % frame_count
+    } else {
+        frame.min(frame_count - 1)
+    }
+}
+
+// Ce que fait le joueur pendant le pas, d'après quoi l'animation est choisie
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AnimationInput {
+    pub is_jumping: bool,
+    pub is_charging: bool,
+    pub velocity_y: f32,
+    pub direction: f32, // -1.0, 0.0 ou 1.0
+    pub is_dead: bool,
+}
+
+// Machine à états de l'animation : l'état suit celui du joueur, le temps
+// écoulé dans l'état donne l'image affichée
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Animation {
+    state: AnimationState,
+    time: f32, // Temps écoulé depuis l'entrée dans l'état
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Animation::new()
+    }
+}
+
+impl Animation {
+    pub fn new() -> Self {
+        Animation {
+            state: AnimationState::Idle,
+            time: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, delta_time: f32, input: &AnimationInput) {
+        self.time += delta_time;
+
+        let next = self.next_state(input);
+        if next != self.state {
+            self.state = next;
+            self.time = 0.0;
+        }
+    }
+
+    // Par priorité : mort, saut, charge, fin de la réception, puis course
+    // ou repos selon la direction
+    fn next_state(&self, input: &AnimationInput) -> AnimationState {
+        if input.is_dead {
+            return AnimationState::Dying;
+        }
+        if input.is_jumping {
+            return if input.velocity_y < 0.0 {
+                AnimationState::Rising
+            } else {
+                AnimationState::Falling
+            };
+        }
+        if input.is_charging {
+            return AnimationState::Charging;
+        }
+        if self.state.is_airborne()
+            || (self.state == AnimationState::Landing && !self.is_finished())
+        {
+            return AnimationState::Landing;
+        }
+
+        if input.direction < 0.0 {
+            AnimationState::RunLeft
+        } else if input.direction > 0.0 {
+            AnimationState::RunRight
+        } else {
+            AnimationState::Idle
+        }
+    }
+
+    pub fn get_state(&self) -> AnimationState {
+        self.state
+    }
+
+    pub fn get_time(&self) -> f32 {
+        self.time
+    }
+
+    // Image du dessin procédural
+    pub fn get_frame(&self) -> usize {
+        frame_at(
+            self.time,
+            self.state.get_frame_duration(),
+            self.state.get_frame_count(),
+            self.state.is_looping(),
+        )
+    }
+
+    // Une animation jouée une fois est-elle arrivée au bout ?
+    pub fn is_finished(&self) -> bool {
+        let duration = self.state.get_frame_duration() * self.state.get_frame_count() as f32;
+        !self.state.is_looping() && self.time >= duration
+    }
+
+    // Animation à chercher dans la feuille de sprites
+    pub fn get_sprite_animation(&self) -> SpriteAnimation {
+        SpriteAnimation {
+            name: self.state.get_name(),
+            time: self.time,
+            frame_duration: self.state.get_frame_duration(),
+            looping: self.state.is_looping(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEP: f32 = 1.0 / 120.0;
+
+    fn run(animation: &mut Animation, seconds: f32, input: AnimationInput) {
+        for _ in 0..(seconds / STEP).round() as usize {
+            animation.update(STEP, &input);
+        }
+    }
+
+    fn moving(direction: f32) -> AnimationInput {
+        AnimationInput {
+            direction,
+            ..Default::default()
+        }
+    }
+
+    fn airborne(velocity_y: f32) -> AnimationInput {
+        AnimationInput {
+            is_jumping: true,
+            velocity_y,
+            ..Default::default()
+        }
+    }
+
+    fn charging() -> AnimationInput {
+        AnimationInput {
+            is_charging: true,
+            ..Default::default()
+        }
+    }
+
+    fn dead() -> AnimationInput {
+        AnimationInput {
+            is_dead: true,
+            ..Default::default()
+        }
+    }
+
+    // Un saut complet : repos, course, charge, montée, descente, réception
+    // puis retour au repos une fois la réception jouée
+    #[test]
+    fn jump_cycle_goes_through_every_state() {
+        let mut animation = Animation::new();
+        assert_eq!(animation.get_state(), AnimationState::Idle);
+
+        let steps = [
+            (moving(1.0), AnimationState::RunRight),
+            (moving(-1.0), AnimationState::RunLeft),
+            (charging(), AnimationState::Charging),
+            (airborne(-300.0), AnimationState::Rising),
+            (airborne(300.0), AnimationState::Falling),
+            (moving(0.0), AnimationState::Landing),
+        ];
+        for (input, expected) in steps {
+            animation.update(STEP, &input);
+            assert_eq!(animation.get_state(), expected);
+            assert_eq!(animation.get_time(), 0.0);
+        }
+
+        // La réception va jusqu'au bout même si le joueur repart aussitôt
+        run(&mut animation, 0.1, moving(1.0));
+        assert_eq!(animation.get_state(), AnimationState::Landing);
+        run(&mut animation, 0.1, moving(0.0));
+        assert_eq!(animation.get_state(), AnimationState::Idle);
+    }
+
+    #[test]
+    fn any_state_can_die() {
+        let inputs = [
+            moving(0.0),
+            moving(1.0),
+            moving(-1.0),
+            charging(),
+            airborne(-300.0),
+            airborne(300.0),
+        ];
+        for input in inputs {
+            let mut animation = Animation::new();
+            animation.update(STEP, &input);
+            animation.update(STEP, &dead());
+            assert_eq!(animation.get_state(), AnimationState::Dying);
+
+            // L'animation de mort reste figée sur sa dernière image
+            run(&mut animation, 2.0, dead());
+            assert_eq!(animation.get_state(), AnimationState::Dying);
+            assert!(animation.is_finished());
+            assert_eq!(
+                animation.get_frame(),
+                AnimationState::Dying.get_frame_count() - 1
+            );
+        }
+
+        // Mourir pendant la réception l'interrompt aussi
+        let mut animation = Animation::new();
+        animation.update(STEP, &airborne(300.0));
+        animation.update(STEP, &moving(0.0));
+        assert_eq!(animation.get_state(), AnimationState::Landing);
+        animation.update(STEP, &dead());
+        assert_eq!(animation.get_state(), AnimationState::Dying);
+    }
+
+    #[test]
+    fn frame_follows_elapsed_time() {
+        // 10 images par seconde : une image toutes les 0,1 s
+        assert_eq!(frame_at(0.0, 1.0 / 10.0, 4, true), 0);
+        assert_eq!(frame_at(0.05, 1.0 / 10.0, 4, true), 0);
+        assert_eq!(frame_at(0.15, 1.0 / 10.0, 4, true), 1);
+        assert_eq!(frame_at(0.35, 1.0 / 10.0, 4, true), 3);
+        // Une boucle recommence, une animation jouée une fois s'arrête
+        assert_eq!(frame_at(0.45, 1.0 / 10.0, 4, true), 0);
+        assert_eq!(frame_at(0.45, 1.0 / 10.0, 4, false), 3);
+        assert_eq!(frame_at(-1.0, 1.0 / 10.0, 4, true), 0);
+
+        // Repos à 4 images par seconde (0,25 s par image)
+        let mut animation = Animation::new();
+        run(&mut animation, 0.6, moving(0.0));
+        assert_eq!(animation.get_frame(), 2);
+        run(&mut animation, 0.5, moving(0.0));
+        assert_eq!(animation.get_frame(), 0);
+
+        // La course va à 10 images par seconde
+        run(&mut animation, 0.35, moving(1.0));
+        assert_eq!(animation.get_state(), AnimationState::RunRight);
+        assert_eq!(animation.get_frame(), 3);
+    }
+}
@@ -0,0 +1,201 @@
+use rand::Rng;
+
+use crate::color::Color;
+use crate::obstacle::ObstacleType;
+use crate::rng::GameRng;
+use crate::scenery::SceneryType;
+
+// Distance parcourue dans chaque biome (pixels du monde, 12 tronçons de décor)
+pub const BIOME_LENGTH: f32 = 6144.0;
+
+// Fin de biome sur laquelle on passe progressivement au suivant
+pub const BIOME_TRANSITION_LENGTH: f32 = 1536.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BiomeType {
+    Forest,
+    Desert,
+    Snow,
+    NightCity,
+}
+
+// Couleurs d'un biome
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Palette {
+    pub sky: Color,
+    pub ground: Color,     // Sous-sol, jusqu'au bas de l'écran
+    pub surface: Color,    // Bande de sol sur laquelle marche le joueur
+    pub grass: Color,      // Touffes d'herbe
+    pub background: Color, // Montagnes, dunes ou immeubles
+    pub cloud: Color,
+    pub trunk: Color,   // Tronc des arbres, poteau des lampadaires
+    pub foliage: Color, // Feuillage, cactus, lumières de la ville
+    pub bush: Color,    // Avant-plan
+}
+
+// Définition d'un biome : décor, couleurs et obstacles
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Biome {
+    pub biome_type: BiomeType,
+    pub name: &'static str,
+    pub palette: Palette,
+    pub background: SceneryType,    // Élément d'arrière-plan
+    pub landmark: SceneryType,      // Élément principal posé sur le sol
+    pub cloud_chance: f64,          // Probabilité d'un nuage par tronçon
+    pub bush_chance: f64,           // Probabilité d'un élément d'avant-plan par tronçon
+    pub max_grass_tufts: u32,       // Par tronçon
+    pub obstacle_weights: [u32; 3], // Poids des piques, blocs et plateformes
+}
+
+// Biomes dans l'ordre où on les traverse en allant vers la droite (le
+// parcours boucle ensuite, et se répète aussi vers la gauche)
+const BIOMES: [Biome; 4] = [
+    Biome {
+        biome_type: BiomeType::Forest,
+        name: "Forêt",
+        palette: Palette {
+            sky: Color::SKYBLUE,
+            ground: Color::GREEN,
+            surface: Color::new(76, 153, 0, 255), // Vert herbe
+            grass: Color::new(102, 204, 0, 255),  // Vert herbe plus vif
+            background: Color::new(128, 128, 128, 255), // Gris
+            cloud: Color::WHITE,
+            trunk: Color::new(139, 69, 19, 255),   // Marron
+            foliage: Color::new(34, 139, 34, 255), // Vert forêt
+            bush: Color::new(20, 100, 20, 255),    // Vert sombre
+        },
+        background: SceneryType::Mountain,
+        landmark: SceneryType::Tree,
+        cloud_chance: 0.8,
+        bush_chance: 0.9,
+        max_grass_tufts: 3,
+        obstacle_weights: [1, 1, 1],
+    },
+    Biome {
+        biome_type: BiomeType::Desert,
+        name: "Désert",
+        palette: Palette {
+            sky: Color::new(250, 214, 165, 255),
+            ground: Color::new(237, 201, 132, 255),
+            surface: Color::new(214, 170, 98, 255),
+            grass: Color::new(160, 150, 60, 255), // Herbe sèche
+            background: Color::new(222, 184, 120, 255), // Dunes
+            cloud: Color::new(255, 248, 235, 255),
+            trunk: Color::new(120, 90, 50, 255),
+            foliage: Color::new(58, 135, 70, 255), // Cactus
+            bush: Color::new(150, 120, 70, 255),   // Broussailles
+        },
+        background: SceneryType::Mountain,
+        landmark: SceneryType::Cactus,
+        cloud_chance: 0.2,
+        bush_chance: 0.5,
+        max_grass_tufts: 1,
+        obstacle_weights: [3, 1, 1],
+    },
+    Biome {
+        biome_type: BiomeType::Snow,
+        name: "Neige",
+        palette: Palette {
+            sky: Color::new(200, 220, 240, 255),
+            ground: Color::new(240, 245, 250, 255),
+            surface: Color::new(220, 230, 240, 255),
+            grass: Color::new(180, 200, 210, 255),
+            background: Color::new(170, 180, 200, 255),
+            cloud: Color::new(245, 245, 250, 255),
+            trunk: Color::new(90, 60, 40, 255),
+            foliage: Color::new(30, 90, 60, 255), // Sapins
+            bush: Color::new(230, 238, 245, 255), // Congères
+        },
+        background: SceneryType::Mountain,
+        landmark: SceneryType::Tree,
+        cloud_chance: 0.9,
+        bush_chance: 0.9,
+        max_grass_tufts: 2,
+        obstacle_weights: [1, 3, 1],
+    },
+    Biome {
+        biome_type: BiomeType::NightCity,
+        name: "Ville de nuit",
+        palette: Palette {
+            sky: Color::new(25, 25, 60, 255),
+            ground: Color::new(45, 45, 55, 255),
+            surface: Color::new(70, 70, 80, 255),    // Trottoir
+            grass: Color::new(90, 90, 100, 255),     // Débris
+            background: Color::new(40, 40, 70, 255), // Immeubles
+            cloud: Color::new(60, 60, 90, 255),      // Brume
+            trunk: Color::new(60, 60, 70, 255),      // Lampadaires
+            foliage: Color::new(255, 220, 120, 255), // Lumières
+            bush: Color::new(30, 30, 45, 255),       // Barrières
+        },
+        background: SceneryType::Building,
+        landmark: SceneryType::StreetLamp,
+        cloud_chance: 0.5,
+        bush_chance: 0.6,
+        max_grass_tufts: 2,
+        obstacle_weights: [1, 1, 3],
+    },
+];
+
+pub fn get_biome(biome_type: BiomeType) -> &'static Biome {
+    BIOMES
+        .iter()
+        .find(|biome| biome.biome_type == biome_type)
+        .unwrap_or(&BIOMES[0])
+}
+
+// Biome de la zone qui contient `x`, biome suivant et part du suivant dans
+// le mélange (0.0 hors des transitions, jusqu'à 1.0 à la frontière)
+pub fn biome_blend_at(x: f32) -> (&'static Biome, &'static Biome, f32) {
+    let zone = (x / BIOME_LENGTH).floor() as i64;
+    let current = &BIOMES[zone.rem_euclid(BIOMES.len() as i64) as usize];
+    let next = &BIOMES[(zone + 1).rem_euclid(BIOMES.len() as i64) as usize];
+
+    let transition_start = (zone + 1) as f32 * BIOME_LENGTH - BIOME_TRANSITION_LENGTH;
+    let t = ((x - transition_start) / BIOME_TRANSITION_LENGTH).clamp(0.0, 1.0);
+
+    // Départ et arrivée en douceur
+    (current, next, t * t * (3.0 - 2.0 * t))
+}
+
+// Couleurs mélangées au point `x` (ciel et sol changent progressivement)
+pub fn palette_at(x: f32) -> Palette {
+    let (current, next, t) = biome_blend_at(x);
+    let (a, b) = (&current.palette, &next.palette);
+    Palette {
+        sky: a.sky.lerp(b.sky, t),
+        ground: a.ground.lerp(b.ground, t),
+        surface: a.surface.lerp(b.surface, t),
+        grass: a.grass.lerp(b.grass, t),
+        background: a.background.lerp(b.background, t),
+        cloud: a.cloud.lerp(b.cloud, t),
+        trunk: a.trunk.lerp(b.trunk, t),
+        foliage: a.foliage.lerp(b.foliage, t),
+        bush: a.bush.lerp(b.bush, t),
+    }
+}
+
+// Biome d'un élément placé en `x` : dans une transition, les éléments des deux
+// biomes se mêlent, ceux du suivant de plus en plus nombreux
+pub fn pick_biome(x: f32, rng: &mut GameRng) -> &'static Biome {
+    let (current, next, t) = biome_blend_at(x);
+    if rng.random::<f32>() < t {
+        next
+    } else {
+        current
+    }
+}
+
+impl Biome {
+    // Tirer un type d'obstacle selon les poids du biome
+    pub fn pick_obstacle(&self, rng: &mut GameRng) -> ObstacleType {
+        let [spike, block, platform] = self.obstacle_weights;
+        let roll = rng.random_range(0..spike + block + platform);
+        if roll < spike {
+            ObstacleType::Spike
+        } else if roll < spike + block {
+            ObstacleType::Block
+        } else {
+            ObstacleType::FloatingPlatform
+        }
+    }
+}
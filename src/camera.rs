@@ -0,0 +1,269 @@
+use crate::config::CameraConfig;
+use crate::math::Vector2;
+
+// Décalage maximal de la vue quand le traumatisme est à son maximum (pixels)
+const MAX_SHAKE_OFFSET: f32 = 20.0;
+
+// Perte de traumatisme par seconde
+const TRAUMA_DECAY: f32 = 1.5;
+
+// Caméra qui suit le joueur : zone morte horizontale, anticipation dans le
+// sens de la marche, lissage du suivi et montée quand le joueur est haut
+#[derive(Clone, Debug)]
+pub struct Camera {
+    position: Vector2,          // Coin haut gauche de la vue dans le monde
+    previous_position: Vector2, // Position au pas de simulation précédent
+    look_ahead: f32,            // Décalage actuel vers l'avant (lissé)
+    view_width: f32,
+    view_height: f32,
+    config: CameraConfig,
+    trauma: f32,     // Intensité du tremblement, de 0.0 à 1.0
+    shake_time: f32, // Temps écoulé, fait varier le tremblement
+    shake_enabled: bool,
+}
+
+impl Camera {
+    // Caméra au repos, centrée horizontalement sur `target`
+    pub fn new(config: CameraConfig, view_width: f32, view_height: f32, target: Vector2) -> Self {
+        let position = Vector2::new(target.x - view_width / 2.0, 0.0);
+        Camera {
+            position,
+            previous_position: position,
+            look_ahead: 0.0,
+            view_width,
+            view_height,
+            config,
+            trauma: 0.0,
+            shake_time: 0.0,
+            shake_enabled: true,
+        }
+    }
+
+    pub fn set_config(&mut self, config: CameraConfig) {
+        self.config = config;
+    }
+
+    // Changer la largeur de la vue sans déplacer son centre
+    pub fn set_view_width(&mut self, view_width: f32) {
+        let shift = (self.view_width - view_width) / 2.0;
+        self.position.x += shift;
+        self.previous_position.x += shift;
+        self.view_width = view_width;
+    }
+
+    // Suivre `target` (position du joueur dans le monde) qui se déplace dans
+    // la direction `direction` (-1.0, 0.0 ou 1.0)
+    pub fn update(&mut self, delta_time: f32, target: Vector2, direction: f32) {
+        self.previous_position = self.position;
+
+        // Part du retard rattrapée pendant ce pas (indépendante du pas de temps)
+        let follow = if self.config.follow_speed > 0.0 {
+            1.0 - (-self.config.follow_speed * delta_time).exp()
+        } else {
+            1.0
+        };
+
+        // L'anticipation garde la dernière direction quand le joueur s'arrête
+        if direction != 0.0 {
+            let look_ahead = direction.signum() * self.config.look_ahead;
+            self.look_ahead += (look_ahead - self.look_ahead) * follow;
+        }
+
+        // Horizontal : le point visé peut bouger librement dans la zone morte
+        let focus = target.x + self.look_ahead;
+        let center = self.position.x + self.view_width / 2.0;
+        let half_dead_zone = self.config.dead_zone_width / 2.0;
+        let desired_center = if focus > center + half_dead_zone {
+            focus - half_dead_zone
+        } else if focus < center - half_dead_zone {
+            focus + half_dead_zone
+        } else {
+            center
+        };
+        self.position.x += (desired_center - center) * follow;
+
+        // Vertical : la vue reste posée sur le sol, sauf quand le joueur monte
+        // plus haut que la marge en haut de l'écran
+        let desired_y = (target.y - self.config.vertical_margin).min(0.0);
+        self.position.y += (desired_y - self.position.y) * follow;
+    }
+
+    // Secouer la vue (choc, atterrissage brutal) ; les traumatismes s'additionnent
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.0);
+    }
+
+    // Faire retomber le tremblement, en temps réel (il continue pendant un
+    // arrêt sur image et après la fin de la partie)
+    pub fn update_shake(&mut self, frame_time: f32) {
+        self.trauma = (self.trauma - TRAUMA_DECAY * frame_time).max(0.0);
+        self.shake_time += frame_time;
+    }
+
+    // Désactiver le tremblement (joueurs sensibles aux mouvements de l'écran)
+    pub fn set_shake_enabled(&mut self, enabled: bool) {
+        self.shake_enabled = enabled;
+    }
+
+    // Décalage actuel de la vue dû au tremblement ; l'amplitude croît avec le
+    // carré du traumatisme pour que les petits chocs restent discrets
+    pub fn get_shake_offset(&self) -> Vector2 {
+        if !self.shake_enabled || self.trauma <= 0.0 {
+            return Vector2::default();
+        }
+
+        // Somme de sinus de fréquences sans rapport simple : mouvement
+        // irrégulier sans toucher au générateur aléatoire de la partie
+        let t = self.shake_time;
+        let noise_x = ((t * 47.0).sin() + (t * 73.0 + 1.7).sin()) / 2.0;
+        let noise_y = ((t * 53.0 + 0.5).sin() + (t * 61.0 + 2.9).sin()) / 2.0;
+        let amplitude = MAX_SHAKE_OFFSET * self.trauma * self.trauma;
+        Vector2::new(noise_x * amplitude, noise_y * amplitude)
+    }
+
+    pub fn get_trauma(&self) -> f32 {
+        self.trauma
+    }
+
+    // Copie de la caméra interpolée entre les deux derniers pas de simulation
+    pub fn interpolate(&self, alpha: f32) -> Camera {
+        let mut camera = self.clone();
+        camera.position.x =
+            self.previous_position.x + (self.position.x - self.previous_position.x) * alpha;
+        camera.position.y =
+            self.previous_position.y + (self.position.y - self.previous_position.y) * alpha;
+        camera
+    }
+
+    // Position à l'écran (coordonnées logiques) d'un point du monde
+    pub fn world_to_screen(&self, world: Vector2) -> Vector2 {
+        self.world_to_screen_parallax(world, 1.0)
+    }
+
+    // Idem pour un plan de décor qui défile plus lentement (facteur < 1.0) ;
+    // le tremblement décale tous les plans de la même façon
+    pub fn world_to_screen_parallax(&self, world: Vector2, parallax_factor: f32) -> Vector2 {
+        let shake = self.get_shake_offset();
+        Vector2::new(
+            world.x - self.position.x * parallax_factor + shake.x,
+            world.y - self.position.y * parallax_factor + shake.y,
+        )
+    }
+
+    // Position dans le monde d'un point de l'écran
+    pub fn screen_to_world(&self, screen: Vector2) -> Vector2 {
+        self.screen_to_world_parallax(screen, 1.0)
+    }
+
+    // Idem pour un plan de décor de facteur de parallaxe `parallax_factor`
+    pub fn screen_to_world_parallax(&self, screen: Vector2, parallax_factor: f32) -> Vector2 {
+        let shake = self.get_shake_offset();
+        Vector2::new(
+            screen.x + self.position.x * parallax_factor - shake.x,
+            screen.y + self.position.y * parallax_factor - shake.y,
+        )
+    }
+
+    // Un objet de largeur `width` placé en `screen_x` est-il dans la vue ?
+    pub fn is_visible(&self, screen_x: f32, width: f32) -> bool {
+        screen_x + width >= 0.0 && screen_x <= self.view_width
+    }
+
+    pub fn get_position(&self) -> Vector2 {
+        self.position
+    }
+
+    pub fn get_view_width(&self) -> f32 {
+        self.view_width
+    }
+
+    pub fn get_view_height(&self) -> f32 {
+        self.view_height
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GameConfig;
+
+    const STEP: f32 = 1.0 / 120.0;
+
+    // Caméra sans lissage, dans une vue de 800x600 centrée sur x = 400
+    fn camera(look_ahead: f32, dead_zone_width: f32) -> Camera {
+        let config = CameraConfig {
+            follow_speed: 0.0,
+            look_ahead,
+            dead_zone_width,
+            ..CameraConfig::default()
+        };
+        Camera::new(config, 800.0, 600.0, Vector2::new(400.0, 490.0))
+    }
+
+    fn center_x(camera: &Camera) -> f32 {
+        camera.get_position().x + camera.get_view_width() / 2.0
+    }
+
+    #[test]
+    fn dead_zone_lets_the_player_move_freely() {
+        let mut camera = camera(0.0, 80.0);
+
+        camera.update(STEP, Vector2::new(430.0, 490.0), 0.0);
+        assert_eq!(center_x(&camera), 400.0);
+
+        // Hors de la zone morte, la vue suit en gardant le joueur à son bord
+        camera.update(STEP, Vector2::new(450.0, 490.0), 0.0);
+        assert_eq!(center_x(&camera), 410.0);
+        camera.update(STEP, Vector2::new(380.0, 490.0), 0.0);
+        assert_eq!(center_x(&camera), 410.0);
+        camera.update(STEP, Vector2::new(350.0, 490.0), 0.0);
+        assert_eq!(center_x(&camera), 390.0);
+    }
+
+    #[test]
+    fn look_ahead_follows_the_last_direction() {
+        let mut camera = camera(100.0, 0.0);
+
+        camera.update(STEP, Vector2::new(400.0, 490.0), 1.0);
+        assert_eq!(center_x(&camera), 500.0);
+        camera.update(STEP, Vector2::new(400.0, 490.0), 0.0);
+        assert_eq!(center_x(&camera), 500.0);
+        camera.update(STEP, Vector2::new(400.0, 490.0), -1.0);
+        assert_eq!(center_x(&camera), 300.0);
+
+        // Avec lissage, l'anticipation s'installe progressivement
+        let config = CameraConfig::default();
+        let mut camera = Camera::new(config.clone(), 800.0, 600.0, Vector2::new(400.0, 490.0));
+        let mut previous = center_x(&camera);
+        for _ in 0..240 {
+            camera.update(STEP, Vector2::new(400.0, 490.0), 1.0);
+            assert!(center_x(&camera) >= previous);
+            previous = center_x(&camera);
+        }
+        let settled = 400.0 + config.look_ahead - config.dead_zone_width / 2.0;
+        assert!((center_x(&camera) - settled).abs() < 0.01);
+    }
+
+    #[test]
+    fn camera_rises_at_the_top_of_a_full_jump() {
+        let config = GameConfig::default();
+        let ground_level = config.ground_level();
+        let apex = ground_level - config.physics.jump_height;
+        let mut camera = camera(0.0, 0.0);
+
+        // Au sol et à mi-hauteur, la vue reste posée sur le sol
+        camera.update(STEP, Vector2::new(400.0, ground_level), 0.0);
+        assert_eq!(camera.get_position().y, 0.0);
+        camera.update(STEP, Vector2::new(400.0, (ground_level + apex) / 2.0), 0.0);
+        assert_eq!(camera.get_position().y, 0.0);
+
+        // Au sommet d'un saut complet avec les réglages par défaut, elle monte
+        camera.update(STEP, Vector2::new(400.0, apex), 0.0);
+        let rise = config.camera.vertical_margin - apex;
+        assert!(rise > 0.0);
+        assert_eq!(camera.get_position().y, -rise);
+
+        camera.update(STEP, Vector2::new(400.0, ground_level), 0.0);
+        assert_eq!(camera.get_position().y, 0.0);
+    }
+}
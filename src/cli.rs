@@ -0,0 +1,300 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::config::DEFAULT_CONFIG_PATH;
+use crate::sprite::DEFAULT_ASSETS_DIR;
+use crate::state::StateId;
+
+pub const USAGE: &str = "\
+Utilisation : vibegame [options]
+
+Options :
+  --seed <nombre>        Graine du monde (réutilisée à chaque nouvelle partie)
+  --width <pixels>       Largeur de la fenêtre (remplace window.width)
+  --height <pixels>      Hauteur de la fenêtre (remplace window.height)
+  --fullscreen           Lancer en plein écran
+  --config <fichier>     Fichier de configuration (défaut : vibegame.toml)
+  --assets <dossier>     Dossier des sprites (défaut : assets)
+  --replay <fichier>     Rejouer un enregistrement à la place du clavier
+  --headless             Simuler sans fenêtre et afficher le résultat
+  --frames <nombre>      Nombre de frames simulées en mode --headless
+  --screenshot <png>     Image de la dernière frame en mode --headless
+  --mode <nom>           Écran de départ : title, play ou settings
+  -h, --help             Afficher cette aide";
+
+// Écran sur lequel le jeu démarre
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LaunchMode {
+    Title,    // Écran titre (par défaut)
+    Play,     // Directement en jeu
+    Settings, // Écran des paramètres
+}
+
+impl LaunchMode {
+    pub fn from_name(name: &str) -> Option<LaunchMode> {
+        match name {
+            "title" => Some(LaunchMode::Title),
+            "play" => Some(LaunchMode::Play),
+            "settings" => Some(LaunchMode::Settings),
+            _ => None,
+        }
+    }
+
+    pub fn get_initial_state(&self) -> StateId {
+        match self {
+            LaunchMode::Title => StateId::Title,
+            LaunchMode::Play => StateId::Playing,
+            LaunchMode::Settings => StateId::Settings,
+        }
+    }
+}
+
+// Options de lancement lues sur la ligne de commande
+#[derive(Clone, Debug, PartialEq)]
+pub struct LaunchOptions {
+    pub seed: Option<u64>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub fullscreen: bool,
+    pub config_path: PathBuf,
+    pub assets_path: PathBuf,
+    pub replay_path: Option<PathBuf>,
+    pub headless: bool,
+    pub frames: Option<usize>,
+    pub screenshot_path: Option<PathBuf>,
+    pub mode: LaunchMode,
+    pub show_help: bool,
+}
+
+impl Default for LaunchOptions {
+    fn default() -> Self {
+        LaunchOptions {
+            seed: None,
+            width: None,
+            height: None,
+            fullscreen: false,
+            config_path: PathBuf::from(DEFAULT_CONFIG_PATH),
+            assets_path: PathBuf::from(DEFAULT_ASSETS_DIR),
+            replay_path: None,
+            headless: false,
+            frames: None,
+            screenshot_path: None,
+            mode: LaunchMode::Title,
+            show_help: false,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CliError(String);
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl LaunchOptions {
+    // Lire les arguments (sans le nom du programme)
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<LaunchOptions, CliError> {
+        let mut options = LaunchOptions::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => options.seed = Some(parse_number(&arg, args.next())?),
+                "--width" => options.width = Some(parse_number(&arg, args.next())?),
+                "--height" => options.height = Some(parse_number(&arg, args.next())?),
+                "--fullscreen" => options.fullscreen = true,
+                "--config" => options.config_path = PathBuf::from(expect_value(&arg, args.next())?),
+                "--assets" => options.assets_path = PathBuf::from(expect_value(&arg, args.next())?),
+                "--replay" => {
+                    options.replay_path = Some(PathBuf::from(expect_value(&arg, args.next())?))
+                }
+                "--headless" => options.headless = true,
+                "--frames" => options.frames = Some(parse_number(&arg, args.next())?),
+                "--screenshot" => {
+                    options.screenshot_path = Some(PathBuf::from(expect_value(&arg, args.next())?))
+                }
+                "--mode" => {
+                    let name = expect_value(&arg, args.next())?;
+                    options.mode = LaunchMode::from_name(&name).ok_or_else(|| {
+                        CliError(format!(
+                            "--mode : mode inconnu \"{}\" (title, play ou settings)",
+                            name
+                        ))
+                    })?;
+                }
+                "-h" | "--help" => options.show_help = true,
+                _ => return Err(CliError(format!("option inconnue \"{}\"", arg))),
+            }
+        }
+
+        // L'aide est affichée même si les options ne vont pas ensemble
+        if !options.show_help {
+            options.check()?;
+        }
+        Ok(options)
+    }
+
+    // Refuser les combinaisons d'options qui n'ont pas de sens
+    fn check(&self) -> Result<(), CliError> {
+        if self.width == Some(0) || self.height == Some(0) {
+            return Err(CliError(
+                "--width et --height doivent être strictement positifs".to_string(),
+            ));
+        }
+        if !self.headless {
+            if self.frames.is_some() {
+                return Err(CliError("--frames nécessite --headless".to_string()));
+            }
+            if self.screenshot_path.is_some() {
+                return Err(CliError("--screenshot nécessite --headless".to_string()));
+            }
+        } else if self.replay_path.is_none() && self.frames.is_none() {
+            return Err(CliError(
+                "--headless nécessite --replay <fichier> ou --frames <nombre>".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn expect_value(option: &str, value: Option<String>) -> Result<String, CliError> {
+    match value {
+        Some(value) if !value.starts_with("--") => Ok(value),
+        _ => Err(CliError(format!("{} attend une valeur", option))),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(option: &str, value: Option<String>) -> Result<T, CliError> {
+    let value = expect_value(option, value)?;
+    value.parse::<T>().map_err(|_| {
+        CliError(format!(
+            "{} attend un entier positif (valeur : \"{}\")",
+            option, value
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<LaunchOptions, CliError> {
+        LaunchOptions::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn error(args: &[&str]) -> String {
+        parse(args).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn no_arguments_give_defaults() {
+        assert_eq!(parse(&[]).unwrap(), LaunchOptions::default());
+    }
+
+    #[test]
+    fn every_flag_is_read() {
+        let options = parse(&[
+            "--seed",
+            "42",
+            "--width",
+            "1024",
+            "--height",
+            "768",
+            "--fullscreen",
+            "--config",
+            "tuning.toml",
+            "--assets",
+            "art",
+            "--mode",
+            "play",
+        ])
+        .unwrap();
+        assert_eq!(options.seed, Some(42));
+        assert_eq!(options.width, Some(1024));
+        assert_eq!(options.height, Some(768));
+        assert!(options.fullscreen);
+        assert_eq!(options.config_path, PathBuf::from("tuning.toml"));
+        assert_eq!(options.assets_path, PathBuf::from("art"));
+        assert_eq!(options.mode, LaunchMode::Play);
+        assert!(!options.headless);
+
+        let options = parse(&[
+            "--headless",
+            "--replay",
+            "run.replay",
+            "--frames",
+            "600",
+            "--screenshot",
+            "end.png",
+        ])
+        .unwrap();
+        assert!(options.headless);
+        assert_eq!(options.replay_path, Some(PathBuf::from("run.replay")));
+        assert_eq!(options.frames, Some(600));
+        assert_eq!(options.screenshot_path, Some(PathBuf::from("end.png")));
+
+        assert_eq!(
+            parse(&["--mode", "settings"]).unwrap().mode,
+            LaunchMode::Settings
+        );
+        assert_eq!(parse(&["--mode", "title"]).unwrap().mode, LaunchMode::Title);
+    }
+
+    #[test]
+    fn headless_needs_replay_or_frames() {
+        assert_eq!(
+            error(&["--headless"]),
+            "--headless nécessite --replay <fichier> ou --frames <nombre>"
+        );
+        assert!(parse(&["--headless", "--frames", "10"]).is_ok());
+        assert!(parse(&["--headless", "--replay", "run.replay"]).is_ok());
+    }
+
+    #[test]
+    fn headless_only_options_need_headless() {
+        assert_eq!(error(&["--frames", "10"]), "--frames nécessite --headless");
+        assert_eq!(
+            error(&["--screenshot", "end.png"]),
+            "--screenshot nécessite --headless"
+        );
+    }
+
+    #[test]
+    fn unknown_or_malformed_values_are_refused() {
+        assert_eq!(error(&["--speed"]), "option inconnue \"--speed\"");
+        assert_eq!(error(&["--seed"]), "--seed attend une valeur");
+        assert_eq!(
+            error(&["--config", "--fullscreen"]),
+            "--config attend une valeur"
+        );
+        assert_eq!(
+            error(&["--seed", "abc"]),
+            "--seed attend un entier positif (valeur : \"abc\")"
+        );
+        assert_eq!(
+            error(&["--width", "-5"]),
+            "--width attend un entier positif (valeur : \"-5\")"
+        );
+        assert_eq!(
+            error(&["--height", "0"]),
+            "--width et --height doivent être strictement positifs"
+        );
+        assert_eq!(
+            error(&["--mode", "credits"]),
+            "--mode : mode inconnu \"credits\" (title, play ou settings)"
+        );
+    }
+
+    #[test]
+    fn help_is_shown_even_with_invalid_combinations() {
+        assert!(parse(&["--help"]).unwrap().show_help);
+        assert!(parse(&["-h"]).unwrap().show_help);
+        assert!(parse(&["--headless", "--help"]).unwrap().show_help);
+        assert!(parse(&["--frames", "10", "-h"]).unwrap().show_help);
+    }
+}
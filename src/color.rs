@@ -0,0 +1,61 @@
+// Couleur RGBA utilisée par les éléments du jeu (sans dépendance à raylib)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Color { r, g, b, a }
+    }
+
+    // Mêmes valeurs que les couleurs prédéfinies de raylib
+    pub const WHITE: Color = Color::new(255, 255, 255, 255);
+    pub const BLACK: Color = Color::new(0, 0, 0, 255);
+    pub const GRAY: Color = Color::new(130, 130, 130, 255);
+    pub const DARKGRAY: Color = Color::new(80, 80, 80, 255);
+    pub const RED: Color = Color::new(230, 41, 55, 255);
+    pub const YELLOW: Color = Color::new(253, 249, 0, 255);
+    pub const GREEN: Color = Color::new(0, 228, 48, 255);
+    pub const DARKGREEN: Color = Color::new(0, 117, 44, 255);
+    pub const SKYBLUE: Color = Color::new(102, 191, 255, 255);
+
+    // Couleur éclairée par une lumière `light` (composantes multipliées,
+    // l'opacité est conservée)
+    pub fn tint(self, light: Color) -> Color {
+        let mix = |a: u8, b: u8| (a as u16 * b as u16 / 255) as u8;
+        Color::new(
+            mix(self.r, light.r),
+            mix(self.g, light.g),
+            mix(self.b, light.b),
+            self.a,
+        )
+    }
+
+    // Mélange de deux couleurs (`t` = 0.0 donne `self`, 1.0 donne `other`)
+    pub fn lerp(self, other: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        Color::new(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+            mix(self.a, other.a),
+        )
+    }
+}
+
+#[cfg(feature = "raylib")]
+impl From<Color> for raylib::ffi::Color {
+    fn from(c: Color) -> Self {
+        raylib::ffi::Color {
+            r: c.r,
+            g: c.g,
+            b: c.b,
+            a: c.a,
+        }
+    }
+}
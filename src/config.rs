@@ -0,0 +1,523 @@
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::math::Vector2;
+use crate::obstacle::ObstacleType;
+use crate::viewport::Viewport;
+
+// Fichier de configuration chargé au démarrage s'il existe
+pub const DEFAULT_CONFIG_PATH: &str = "vibegame.toml";
+
+// Intervalle entre deux vérifications du fichier surveillé (secondes)
+const WATCH_POLL_INTERVAL: f32 = 0.5;
+
+// Réglages du jeu, lus depuis un fichier TOML ; toute valeur absente du
+// fichier garde sa valeur par défaut
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameConfig {
+    pub window: WindowConfig,
+    pub physics: PhysicsConfig,
+    pub player: PlayerConfig,
+    pub obstacles: ObstacleConfig,
+    pub camera: CameraConfig,
+    pub day_night: DayNightConfig,
+    pub weather: WeatherConfig,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WindowConfig {
+    pub width: u32,
+    pub height: u32,
+    pub target_fps: u32,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        WindowConfig {
+            width: 800,
+            height: 600,
+            target_fps: 60,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PhysicsConfig {
+    pub ground_height: f32,        // Épaisseur du sol en bas de l'écran
+    pub jump_height: f32,          // Hauteur maximale du saut en pixels
+    pub jump_charge_time_max: f32, // Temps maximal pour charger le saut (en secondes)
+    pub gravity: f32,              // Force de gravité
+    pub player_speed: f32,         // Vitesse de déplacement horizontal
+}
+
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        PhysicsConfig {
+            ground_height: 50.0,
+            jump_height: 200.0,
+            jump_charge_time_max: 0.5,
+            gravity: 1200.0,
+            player_speed: 200.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlayerConfig {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for PlayerConfig {
+    fn default() -> Self {
+        PlayerConfig {
+            width: 40.0,
+            height: 60.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ObstacleConfig {
+    pub min_distance_between_obstacles: f32,
+    pub spike_size: [f32; 2],     // Largeur, hauteur
+    pub block_size: [f32; 2],     // Largeur, hauteur
+    pub platform_size: [f32; 2],  // Largeur, hauteur
+    pub platform_height_min: f32, // Hauteur des plateformes au-dessus du sol
+    pub platform_height_max: f32,
+}
+
+impl Default for ObstacleConfig {
+    fn default() -> Self {
+        ObstacleConfig {
+            min_distance_between_obstacles: 300.0,
+            spike_size: [30.0, 30.0],
+            block_size: [40.0, 40.0],
+            platform_size: [100.0, 20.0],
+            platform_height_min: 60.0,
+            platform_height_max: 100.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CameraConfig {
+    pub follow_speed: f32, // Vitesse de rattrapage du suivi (par seconde, 0 = immédiat)
+    pub look_ahead: f32,   // Anticipation dans le sens de la marche (pixels)
+    pub dead_zone_width: f32, // Zone centrale où le joueur bouge sans que la caméra suive
+    pub vertical_margin: f32, // Au-dessus de cette distance du haut de l'écran, la caméra monte
+}
+
+impl Default for CameraConfig {
+    fn default() -> Self {
+        CameraConfig {
+            follow_speed: 8.0,
+            look_ahead: 100.0,
+            dead_zone_width: 80.0,
+            vertical_margin: 320.0,
+        }
+    }
+}
+
+// Ce qui fait avancer l'heure de la journée
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CycleDriver {
+    Time,     // Durée de la partie
+    Distance, // Distance parcourue depuis le départ
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DayNightConfig {
+    pub enabled: bool, // Sinon l'heure reste celle du départ
+    pub driver: CycleDriver,
+    pub cycle_duration: f32, // Durée d'une journée complète avec "time" (secondes)
+    pub cycle_length: f32,   // Distance d'une journée complète avec "distance" (pixels)
+    pub start_time: f32,     // Heure au départ (0.0 minuit, 0.25 lever, 0.5 midi, 0.75 coucher)
+}
+
+impl Default for DayNightConfig {
+    fn default() -> Self {
+        DayNightConfig {
+            enabled: true,
+            driver: CycleDriver::Time,
+            cycle_duration: 180.0,
+            cycle_length: 30000.0,
+            start_time: 0.35,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WeatherConfig {
+    pub enabled: bool,             // Sinon le temps reste dégagé
+    pub change_interval: f32,      // Durée de chaque période de météo (secondes)
+    pub wind_affects_player: bool, // Le vent dévie les sauts
+}
+
+impl Default for WeatherConfig {
+    fn default() -> Self {
+        WeatherConfig {
+            enabled: true,
+            change_interval: 45.0,
+            wind_affects_player: true,
+        }
+    }
+}
+
+impl ObstacleConfig {
+    pub fn size_of(&self, obstacle_type: &ObstacleType) -> Vector2 {
+        let [width, height] = match obstacle_type {
+            ObstacleType::Spike => self.spike_size,
+            ObstacleType::Block => self.block_size,
+            ObstacleType::FloatingPlatform => self.platform_size,
+        };
+        Vector2::new(width, height)
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        message: String,
+    },
+    Invalid {
+        path: PathBuf,
+        message: String,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, source } => {
+                write!(f, "{}: lecture impossible: {}", path.display(), source)
+            }
+            ConfigError::Parse { path, message } => {
+                write!(f, "{}: fichier invalide: {}", path.display(), message)
+            }
+            ConfigError::Invalid { path, message } => {
+                write!(f, "{}: valeur invalide: {}", path.display(), message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl GameConfig {
+    // Charger et valider le fichier ; s'il n'existe pas, les valeurs par
+    // défaut sont utilisées
+    pub fn load(path: impl AsRef<Path>) -> Result<GameConfig, ConfigError> {
+        GameConfig::load_with(path, |_| {})
+    }
+
+    // Charger le fichier, le corriger avec `adjust` (taille de la fenêtre
+    // passée en ligne de commande...) puis valider le résultat
+    pub fn load_with(
+        path: impl AsRef<Path>,
+        adjust: impl FnOnce(&mut GameConfig),
+    ) -> Result<GameConfig, ConfigError> {
+        let path = path.as_ref();
+        let mut config = match fs::read_to_string(path) {
+            Ok(text) => GameConfig::from_toml(&text, path)?,
+            Err(error) if error.kind() == ErrorKind::NotFound => GameConfig::default(),
+            Err(source) => {
+                return Err(ConfigError::Io {
+                    path: path.to_path_buf(),
+                    source,
+                })
+            }
+        };
+        adjust(&mut config);
+        config.checked(path)
+    }
+
+    // Lire une configuration TOML ; `path` ne sert qu'aux messages d'erreur
+    pub fn parse(text: &str, path: impl AsRef<Path>) -> Result<GameConfig, ConfigError> {
+        let path = path.as_ref();
+        GameConfig::from_toml(text, path)?.checked(path)
+    }
+
+    fn from_toml(text: &str, path: &Path) -> Result<GameConfig, ConfigError> {
+        toml::from_str(text).map_err(|error| ConfigError::Parse {
+            path: path.to_path_buf(),
+            message: error.to_string(),
+        })
+    }
+
+    fn checked(self, path: &Path) -> Result<GameConfig, ConfigError> {
+        self.validate().map_err(|message| ConfigError::Invalid {
+            path: path.to_path_buf(),
+            message,
+        })?;
+        Ok(self)
+    }
+
+    // Configuration au format TOML, relue à l'identique par `parse`
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("configuration sérialisable en TOML")
+    }
+
+    // Vérifier la cohérence des valeurs, en nommant la clé fautive
+    pub fn validate(&self) -> Result<(), String> {
+        fn positive(key: &str, value: f32) -> Result<(), String> {
+            if value.is_finite() && value > 0.0 {
+                Ok(())
+            } else {
+                Err(format!(
+                    "{} doit être strictement positif (valeur : {})",
+                    key, value
+                ))
+            }
+        }
+
+        fn not_negative(key: &str, value: f32) -> Result<(), String> {
+            if value.is_finite() && value >= 0.0 {
+                Ok(())
+            } else {
+                Err(format!(
+                    "{} ne peut pas être négatif (valeur : {})",
+                    key, value
+                ))
+            }
+        }
+
+        positive("window.width", self.window.width as f32)?;
+        positive("window.height", self.window.height as f32)?;
+        positive("window.target_fps", self.window.target_fps as f32)?;
+
+        not_negative("physics.ground_height", self.physics.ground_height)?;
+        positive("physics.jump_height", self.physics.jump_height)?;
+        positive(
+            "physics.jump_charge_time_max",
+            self.physics.jump_charge_time_max,
+        )?;
+        positive("physics.gravity", self.physics.gravity)?;
+        not_negative("physics.player_speed", self.physics.player_speed)?;
+
+        positive("player.width", self.player.width)?;
+        positive("player.height", self.player.height)?;
+
+        if self.ground_level() <= 0.0 {
+            return Err(format!(
+                "window.height ({}) doit dépasser physics.ground_height + player.height ({})",
+                self.window.height,
+                self.physics.ground_height + self.player.height
+            ));
+        }
+
+        let obstacles = &self.obstacles;
+        positive(
+            "obstacles.min_distance_between_obstacles",
+            obstacles.min_distance_between_obstacles,
+        )?;
+        for (key, [width, height]) in [
+            ("obstacles.spike_size", obstacles.spike_size),
+            ("obstacles.block_size", obstacles.block_size),
+            ("obstacles.platform_size", obstacles.platform_size),
+        ] {
+            positive(&format!("{}[0]", key), width)?;
+            positive(&format!("{}[1]", key), height)?;
+        }
+        not_negative(
+            "obstacles.platform_height_min",
+            obstacles.platform_height_min,
+        )?;
+        if obstacles.platform_height_max <= obstacles.platform_height_min {
+            return Err(format!(
+                "obstacles.platform_height_max ({}) doit être supérieur à obstacles.platform_height_min ({})",
+                obstacles.platform_height_max, obstacles.platform_height_min
+            ));
+        }
+
+        let camera = &self.camera;
+        not_negative("camera.follow_speed", camera.follow_speed)?;
+        not_negative("camera.look_ahead", camera.look_ahead)?;
+        not_negative("camera.dead_zone_width", camera.dead_zone_width)?;
+        not_negative("camera.vertical_margin", camera.vertical_margin)?;
+        // Le joueur doit rester visible malgré l'anticipation, même dans la
+        // vue la plus étroite (fenêtre en 4:3, bandes noires sur les côtés)
+        let view_width = Viewport::min_logical_width(self.window.height as f32);
+        if camera.look_ahead + camera.dead_zone_width / 2.0 >= view_width / 2.0 {
+            return Err(format!(
+                "camera.look_ahead + camera.dead_zone_width / 2 ({}) doit rester inférieur à la moitié de la largeur de vue minimale ({})",
+                camera.look_ahead + camera.dead_zone_width / 2.0,
+                view_width / 2.0
+            ));
+        }
+
+        let day_night = &self.day_night;
+        positive("day_night.cycle_duration", day_night.cycle_duration)?;
+        positive("day_night.cycle_length", day_night.cycle_length)?;
+        if !(0.0..=1.0).contains(&day_night.start_time) {
+            return Err(format!(
+                "day_night.start_time doit être compris entre 0.0 et 1.0 (valeur : {})",
+                day_night.start_time
+            ));
+        }
+
+        positive("weather.change_interval", self.weather.change_interval)?;
+
+        Ok(())
+    }
+
+    // Position verticale du joueur posé au sol
+    pub fn ground_level(&self) -> f32 {
+        self.window.height as f32 - self.physics.ground_height - self.player.height
+    }
+}
+
+// Surveille le fichier de configuration et le relit quand il est modifié,
+// pour ajuster le jeu sans le relancer
+pub struct ConfigWatcher {
+    path: PathBuf,
+    window: WindowConfig, // Fenêtre en cours, qui remplace celle du fichier
+    last_modified: Option<SystemTime>,
+    time_since_poll: f32,
+}
+
+impl ConfigWatcher {
+    // `window` : réglages de la fenêtre ouverte (ligne de commande comprise),
+    // avec lesquels la configuration relue est validée
+    pub fn new(path: impl AsRef<Path>, window: WindowConfig) -> Self {
+        let path = path.as_ref().to_path_buf();
+        ConfigWatcher {
+            last_modified: modified_time(&path),
+            path,
+            window,
+            time_since_poll: 0.0,
+        }
+    }
+
+    // Renvoie la nouvelle configuration (ou l'erreur qui la rend inutilisable)
+    // quand le fichier a changé depuis la dernière lecture
+    pub fn poll(&mut self, frame_time: f32) -> Option<Result<GameConfig, ConfigError>> {
+        self.time_since_poll += frame_time;
+        if self.time_since_poll < WATCH_POLL_INTERVAL {
+            return None;
+        }
+        self.time_since_poll = 0.0;
+
+        // Un fichier momentanément absent (enregistrement en cours) est ignoré
+        let modified = modified_time(&self.path)?;
+        if self.last_modified == Some(modified) {
+            return None;
+        }
+        self.last_modified = Some(modified);
+
+        let window = &self.window;
+        Some(GameConfig::load_with(&self.path, |config| {
+            config.window = window.clone()
+        }))
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid(text: &str) -> String {
+        GameConfig::parse(text, "test.toml")
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn missing_file_gives_defaults() {
+        let path = std::env::temp_dir().join("vibegame-config-absente.toml");
+        assert_eq!(GameConfig::load(path).unwrap(), GameConfig::default());
+    }
+
+    #[test]
+    fn missing_keys_keep_their_defaults() {
+        let config = GameConfig::parse(
+            "[physics]\ngravity = 1500.0\n\n[obstacles]\nspike_size = [20.0, 25.0]\n",
+            "test.toml",
+        )
+        .unwrap();
+
+        let mut expected = GameConfig::default();
+        expected.physics.gravity = 1500.0;
+        expected.obstacles.spike_size = [20.0, 25.0];
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn invalid_values_name_the_key() {
+        assert_eq!(
+            invalid("[physics]\ngravity = -1.0\n"),
+            "test.toml: valeur invalide: physics.gravity doit être strictement positif (valeur : -1)"
+        );
+        let message =
+            invalid("[obstacles]\nplatform_height_min = 120.0\nplatform_height_max = 80.0\n");
+        assert!(
+            message.contains("obstacles.platform_height_max (80)"),
+            "{}",
+            message
+        );
+        assert!(
+            message.contains("obstacles.platform_height_min (120)"),
+            "{}",
+            message
+        );
+        let message = invalid("[window]\nwidth = 0\n");
+        assert!(message.contains("window.width"), "{}", message);
+
+        // Une fenêtre large ne suffit pas : la vue peut se réduire au 4:3
+        let message =
+            invalid("[window]\nwidth = 1600\nheight = 600\n\n[camera]\nlook_ahead = 370.0\n");
+        assert!(message.contains("camera.look_ahead"), "{}", message);
+        assert!(message.contains("(400)"), "{}", message);
+    }
+
+    #[test]
+    fn unknown_keys_and_bad_types_are_refused() {
+        let message = invalid("[physics]\ngravty = 1500.0\n");
+        assert!(
+            message.starts_with("test.toml: fichier invalide"),
+            "{}",
+            message
+        );
+        assert!(message.contains("gravty"), "{}", message);
+
+        let message = invalid("[physics]\ngravity = \"fort\"\n");
+        assert!(
+            message.starts_with("test.toml: fichier invalide"),
+            "{}",
+            message
+        );
+        assert!(message.contains("gravity"), "{}", message);
+    }
+}
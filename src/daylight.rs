@@ -0,0 +1,172 @@
+use std::f32::consts::PI;
+
+use rand::Rng;
+
+use crate::color::Color;
+use crate::renderer::Renderer;
+use crate::rng::create_chunk_rng;
+
+// Bandes horizontales du dégradé du ciel
+const SKY_BANDS: i32 = 24;
+
+const STAR_COUNT: usize = 60;
+
+// Flux aléatoire des étoiles, distinct de ceux du décor et des obstacles
+const STAR_RNG_STREAM: u64 = 32;
+
+const MOON_RADIUS: f32 = 22.0;
+const MOON_COLOR: Color = Color::new(245, 245, 220, 255);
+
+// Éclairage à un moment de la journée ; les couleurs du ciel et la lumière
+// multiplient celles du biome
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Lighting {
+    sky_top: Color,
+    sky_bottom: Color,
+    light: Color, // Teinte du décor et des obstacles
+    night: f32,   // Visibilité des étoiles et de la lune, de 0.0 à 1.0
+}
+
+const NIGHT: Lighting = Lighting {
+    sky_top: Color::new(40, 45, 90, 255),
+    sky_bottom: Color::new(70, 80, 130, 255),
+    light: Color::new(110, 120, 170, 255),
+    night: 1.0,
+};
+
+const DAWN: Lighting = Lighting {
+    sky_top: Color::new(150, 150, 210, 255),
+    sky_bottom: Color::new(255, 180, 140, 255),
+    light: Color::new(255, 210, 180, 255),
+    night: 0.3,
+};
+
+const DAY: Lighting = Lighting {
+    sky_top: Color::new(200, 215, 235, 255),
+    sky_bottom: Color::WHITE,
+    light: Color::WHITE,
+    night: 0.0,
+};
+
+const DUSK: Lighting = Lighting {
+    sky_top: Color::new(140, 120, 190, 255),
+    sky_bottom: Color::new(255, 150, 100, 255),
+    light: Color::new(255, 190, 150, 255),
+    night: 0.3,
+};
+
+// Éclairages de référence, interpolés entre eux au fil de la journée
+const KEYFRAMES: [(f32, Lighting); 8] = [
+    (0.0, NIGHT),
+    (0.2, NIGHT),
+    (0.27, DAWN),
+    (0.35, DAY),
+    (0.65, DAY),
+    (0.73, DUSK),
+    (0.8, NIGHT),
+    (1.0, NIGHT),
+];
+
+// Moment de la journée, de 0.0 à 1.0 : 0.0 minuit, 0.25 lever du soleil,
+// 0.5 midi, 0.75 coucher du soleil
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimeOfDay {
+    time: f32,
+}
+
+impl TimeOfDay {
+    // L'heure boucle : 1.2 correspond à 0.2 le lendemain
+    pub fn new(time: f32) -> Self {
+        TimeOfDay {
+            time: time.rem_euclid(1.0),
+        }
+    }
+
+    pub fn get_time(&self) -> f32 {
+        self.time
+    }
+
+    fn get_lighting(&self) -> Lighting {
+        for pair in KEYFRAMES.windows(2) {
+            let ((start, from), (end, to)) = (pair[0], pair[1]);
+            if self.time <= end {
+                let t = (self.time - start) / (end - start);
+                return Lighting {
+                    sky_top: from.sky_top.lerp(to.sky_top, t),
+                    sky_bottom: from.sky_bottom.lerp(to.sky_bottom, t),
+                    light: from.light.lerp(to.light, t),
+                    night: from.night + (to.night - from.night) * t,
+                };
+            }
+        }
+        NIGHT
+    }
+
+    // Haut et bas du ciel pour un biome dont le ciel de jour est `sky`
+    pub fn get_sky_gradient(&self, sky: Color) -> (Color, Color) {
+        let lighting = self.get_lighting();
+        (sky.tint(lighting.sky_top), sky.tint(lighting.sky_bottom))
+    }
+
+    // Lumière qui teinte le décor et les obstacles
+    pub fn get_light(&self) -> Color {
+        self.get_lighting().light
+    }
+
+    // 0.0 en plein jour, 1.0 en pleine nuit
+    pub fn get_night_amount(&self) -> f32 {
+        self.get_lighting().night
+    }
+
+    // Avancement de la lune dans sa course, de 0.0 (lever) à 1.0 (coucher),
+    // ou None pendant la journée
+    fn get_moon_progress(&self) -> Option<f32> {
+        let progress = (self.time + 0.25).rem_euclid(1.0) * 2.0;
+        (progress < 1.0).then_some(progress)
+    }
+
+    // Dessiner le ciel sur tout l'écran : dégradé, puis étoiles et lune la
+    // nuit ; les étoiles sont placées d'après la graine de la partie
+    pub fn draw_sky(&self, d: &mut dyn Renderer, sky: Color, width: f32, height: f32, seed: u64) {
+        let (top, bottom) = self.get_sky_gradient(sky);
+        let band_height = (height / SKY_BANDS as f32).ceil() as i32;
+        for band in 0..SKY_BANDS {
+            let color = top.lerp(bottom, band as f32 / (SKY_BANDS - 1) as f32);
+            d.draw_rectangle(0, band * band_height, width as i32, band_height, color);
+        }
+
+        let night = self.get_night_amount();
+        if night <= 0.0 {
+            return;
+        }
+
+        // Étoiles fixes dans le haut du ciel, plus ou moins brillantes
+        let mut rng = create_chunk_rng(seed, STAR_RNG_STREAM, 0);
+        for _ in 0..STAR_COUNT {
+            let x = rng.random_range(0.0..1.0) * width;
+            let y = rng.random_range(0.0..0.6) * height;
+            let brightness = rng.random_range(0.4..1.0);
+            let alpha = (255.0 * brightness * night) as u8;
+            d.draw_rectangle(x as i32, y as i32, 2, 2, Color::new(255, 255, 240, alpha));
+        }
+
+        // Lune en croissant, qui traverse le ciel pendant la nuit
+        if let Some(progress) = self.get_moon_progress() {
+            let x = width * (0.1 + 0.8 * progress);
+            let y = height * (0.4 - 0.3 * (progress * PI).sin());
+            let alpha = (255.0 * night) as u8;
+            let moon = Color::new(MOON_COLOR.r, MOON_COLOR.g, MOON_COLOR.b, alpha);
+            d.draw_circle(x as i32, y as i32, MOON_RADIUS, moon);
+
+            // Le croissant est découpé par un disque de la couleur du ciel
+            let sky_behind = top.lerp(bottom, y / height);
+            let shadow = Color::new(sky_behind.r, sky_behind.g, sky_behind.b, alpha);
+            d.draw_circle(
+                (x + MOON_RADIUS * 0.5) as i32,
+                (y - MOON_RADIUS * 0.2) as i32,
+                MOON_RADIUS * 0.85,
+                shadow,
+            );
+        }
+    }
+}
@@ -1,9 +1,20 @@
-use raylib::prelude::RaylibDrawHandle;
+use crate::renderer::Renderer;
+
+// Couches de dessin, de l'arrière-plan vers l'avant
+pub const LAYER_BACKGROUND: i32 = 0; // Arrière-plan (montagnes)
+pub const LAYER_MIDDLE: i32 = 1; // Milieu (nuages)
+pub const LAYER_GAMEPLAY: i32 = 2; // Premier plan : sol, arbres, obstacles et joueur
+pub const LAYER_FOREGROUND: i32 = 3; // Avant-plan, dessiné devant le joueur
 
 // Définir un trait pour les éléments qui peuvent être dessinés
 pub trait Drawable {
-    fn draw(&self, d: &mut RaylibDrawHandle, screen_x: f32, screen_width: f32);
+    fn draw(&self, d: &mut dyn Renderer, screen_x: f32, screen_y: f32, screen_width: f32);
     fn get_position(&self) -> (f32, f32);
     fn get_size(&self) -> (f32, f32);
     fn get_layer(&self) -> i32;
-}
\ No newline at end of file
+
+    // Ordre dans la couche : une profondeur plus grande est dessinée par-dessus
+    fn get_depth(&self) -> f32 {
+        0.0
+    }
+}
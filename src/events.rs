@@ -0,0 +1,77 @@
+use crate::math::Vector2;
+use crate::obstacle::ObstacleType;
+
+// Ce qui s'est passé pendant la simulation, pour le son, les particules, les
+// statistiques ou les succès, sans qu'ils aient à lire l'état du joueur ou
+// des obstacles
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameEvent {
+    JumpStarted {
+        charge_percent: f32, // Charge du saut au relâchement, de 0 à 100
+    },
+    Landed {
+        fall_speed: f32, // Vitesse verticale au contact du sol (pixels/s)
+    },
+    ObstacleSpawned {
+        // Une fois par obstacle, même si son tronçon est régénéré
+        obstacle_type: ObstacleType,
+        position: Vector2,
+    },
+    ObstacleCleared {
+        obstacle_type: ObstacleType, // Obstacle dépassé par le joueur
+    },
+    Collided {
+        obstacle_type: ObstacleType,
+    },
+    ScoreMilestone {
+        score: i32, // Palier atteint (multiple de SCORE_MILESTONE)
+    },
+    RunEnded {
+        score: i32,
+        seed: u64,
+    },
+}
+
+// Reçoit les événements de la partie, dans l'ordre où ils se sont produits
+pub trait EventListener {
+    fn on_event(&mut self, event: &GameEvent);
+}
+
+// File des événements produits depuis la dernière distribution
+#[derive(Clone, Debug, Default)]
+pub struct EventQueue {
+    events: Vec<GameEvent>,
+}
+
+impl EventQueue {
+    pub fn new() -> Self {
+        EventQueue { events: Vec::new() }
+    }
+
+    pub fn push(&mut self, event: GameEvent) {
+        self.events.push(event);
+    }
+
+    pub fn get_events(&self) -> &[GameEvent] {
+        &self.events
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    // Récupérer les événements en attente et vider la file
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    // Transmettre chaque événement en attente à tous les abonnés, puis vider
+    // la file
+    pub fn dispatch(&mut self, listeners: &mut [Box<dyn EventListener>]) {
+        for event in self.events.drain(..) {
+            for listener in listeners.iter_mut() {
+                listener.on_event(&event);
+            }
+        }
+    }
+}
@@ -0,0 +1,749 @@
+use crate::biome::{biome_blend_at, palette_at, Biome};
+use crate::camera::Camera;
+use crate::color::Color;
+use crate::config::{CycleDriver, GameConfig};
+use crate::daylight::TimeOfDay;
+use crate::drawable::{Drawable, LAYER_BACKGROUND, LAYER_FOREGROUND, LAYER_GAMEPLAY, LAYER_MIDDLE};
+use crate::events::{EventQueue, GameEvent};
+use crate::math::Vector2;
+use crate::obstacle::ObstacleManager;
+use crate::particles::{EmitterId, ParticleSystem, CHARGE_SPARKLES, HIT_DEBRIS, LANDING_DUST};
+use crate::player::Player;
+use crate::render_queue::RenderQueue;
+use crate::renderer::Renderer;
+use crate::scenery::Scenery;
+use crate::utils::generate_scenery_chunk;
+use crate::viewport::Viewport;
+use crate::weather::Weather;
+
+// Pas de temps fixe de la simulation (120 Hz)
+pub const FIXED_TIME_STEP: f32 = 1.0 / 120.0;
+
+// Durée maximale d'une frame prise en compte, pour éviter d'enchaîner
+// trop de pas de simulation après un gel de la fenêtre
+const MAX_FRAME_TIME: f32 = 0.25;
+
+// Position du joueur dans le monde au départ, fixe pour que le parcours ne
+// dépende pas de la largeur de la fenêtre (seule la caméra la suit)
+const PLAYER_START_X: f32 = 400.0;
+
+// Choc à la mort : tremblement fort et arrêt sur image avant l'écran de fin
+const DEATH_TRAUMA: f32 = 0.8;
+const DEATH_HIT_STOP: f32 = 0.2;
+
+// Atterrissage brutal : chute plus rapide que cette fraction de la vitesse
+// de retour au sol d'un saut complet (sqrt(2 · gravité · hauteur du saut)),
+// soit les sauts chargés à plus de 81 % environ (la vitesse suit la racine
+// de la hauteur)
+const HARD_LANDING_FACTOR: f32 = 0.9;
+const HARD_LANDING_TRAUMA: f32 = 0.45;
+const HARD_LANDING_HIT_STOP: f32 = 0.05;
+
+// Écart entre deux paliers de score signalés par ScoreMilestone
+pub const SCORE_MILESTONE: i32 = 10;
+
+// Opacité de l'avant-plan quand il est transparent, pour laisser voir le joueur
+const FOREGROUND_ALPHA: f32 = 0.5;
+
+// Entrées du joueur pour une frame, indépendantes du périphérique
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Input {
+    pub direction: f32,      // -1.0 (gauche), 0.0 ou 1.0 (droite)
+    pub space_pressed: bool, // Touche de saut maintenue
+}
+
+impl Input {
+    pub fn new(direction: f32, space_pressed: bool) -> Self {
+        Input {
+            direction,
+            space_pressed,
+        }
+    }
+}
+
+// État complet d'une partie, simulé sans fenêtre ni rendu
+pub struct GameState {
+    // Taille de la vue en coordonnées logiques : la hauteur vient de la
+    // configuration, la largeur suit les proportions de la fenêtre
+    screen_width: f32,
+    screen_height: f32,
+
+    // Constantes de jeu
+    config: GameConfig,
+    ground_level: f32,
+
+    seed: u64, // Graine de la partie (décor et obstacles)
+    player: Player,
+    camera: Camera,
+    accumulator: f32,             // Temps écoulé pas encore simulé
+    run_time: f32,                // Temps simulé depuis le début de la partie
+    hit_stop: f32,                // Durée restante de l'arrêt sur image
+    transparent_foreground: bool, // Avant-plan dessiné en transparence
+    scenery: Scenery,
+    obstacle_manager: ObstacleManager,
+    weather: Weather,
+    particles: ParticleSystem,
+    charge_emitter: EmitterId, // Étincelles pendant la charge du saut
+    score: i32,
+    next_milestone: i32, // Prochain palier de score à signaler
+    game_over: bool,
+    events: EventQueue, // Événements pas encore distribués
+}
+
+impl GameState {
+    pub fn new(config: GameConfig, seed: u64) -> Self {
+        let screen_height = config.window.height as f32;
+        let screen_width = Viewport::new(config.window.width as f32, screen_height, screen_height)
+            .get_logical_width();
+        let ground_level = config.ground_level(); // Hauteur du sol
+        let player = Self::spawn_player(&config, ground_level);
+        let camera = Self::spawn_camera(&config, &player, screen_width, screen_height);
+        let mut particles = ParticleSystem::new(seed);
+        let charge_emitter = particles.add_emitter(CHARGE_SPARKLES);
+
+        let mut game = GameState {
+            screen_width,
+            screen_height,
+            player,
+            camera,
+            obstacle_manager: ObstacleManager::new(config.obstacles.clone(), seed, PLAYER_START_X),
+            weather: Weather::new(seed, config.weather.clone()),
+            particles,
+            charge_emitter,
+            config,
+            ground_level,
+            seed,
+            accumulator: 0.0,
+            run_time: 0.0,
+            hit_stop: 0.0,
+            transparent_foreground: true,
+            scenery: Scenery::new(),
+            score: 0,
+            next_milestone: SCORE_MILESTONE,
+            game_over: false,
+            events: EventQueue::new(),
+        };
+        game.stream_scenery();
+        game
+    }
+
+    // Initialiser le joueur à sa position de départ, au niveau du sol
+    fn spawn_player(config: &GameConfig, ground_level: f32) -> Player {
+        Player::new(
+            Vector2::new(PLAYER_START_X, ground_level),
+            Vector2::new(config.player.width, config.player.height),
+        )
+    }
+
+    // Caméra au repos, centrée sur le joueur
+    fn spawn_camera(
+        config: &GameConfig,
+        player: &Player,
+        screen_width: f32,
+        screen_height: f32,
+    ) -> Camera {
+        let (x, y) = player.get_position();
+        Camera::new(
+            config.camera.clone(),
+            screen_width,
+            screen_height,
+            Vector2::new(x, y),
+        )
+    }
+
+    // Recommencer une partie avec la graine donnée (le monde est régénéré,
+    // une même graine redonne exactement le même parcours)
+    pub fn reset(&mut self, seed: u64) {
+        self.seed = seed;
+        self.scenery = Scenery::new();
+        self.game_over = false;
+        self.accumulator = 0.0;
+        self.run_time = 0.0;
+        self.hit_stop = 0.0;
+        self.score = 0;
+        self.next_milestone = SCORE_MILESTONE;
+        self.events = EventQueue::new();
+        self.player = Self::spawn_player(&self.config, self.ground_level);
+        self.camera = Self::spawn_camera(
+            &self.config,
+            &self.player,
+            self.screen_width,
+            self.screen_height,
+        );
+        self.obstacle_manager =
+            ObstacleManager::new(self.config.obstacles.clone(), seed, PLAYER_START_X);
+        self.weather = Weather::new(seed, self.config.weather.clone());
+        self.particles = ParticleSystem::new(seed);
+        self.charge_emitter = self.particles.add_emitter(CHARGE_SPARKLES);
+        self.stream_scenery();
+    }
+
+    // Générer le décor qui approche de la vue et oublier celui qu'elle a
+    // quitté ; chaque tronçon est tiré de la graine et de son index, donc
+    // revenir en arrière redonne exactement le même décor
+    fn stream_scenery(&mut self) {
+        let (seed, ground_level, screen_height) =
+            (self.seed, self.ground_level, self.screen_height);
+        self.scenery.stream(&self.camera, |layer, chunk| {
+            generate_scenery_chunk(layer, chunk, seed, ground_level, screen_height)
+        });
+    }
+
+    // Appliquer en cours de partie les réglages de jeu d'une nouvelle
+    // configuration (gravité, saut, vitesse, espacement des obstacles,
+    // caméra, cycle jour/nuit, météo) ; les dimensions de la fenêtre, du
+    // joueur et des obstacles ne changent qu'au prochain lancement
+    pub fn apply_tuning(&mut self, config: &GameConfig) {
+        let physics = &mut self.config.physics;
+        physics.jump_height = config.physics.jump_height;
+        physics.jump_charge_time_max = config.physics.jump_charge_time_max;
+        physics.gravity = config.physics.gravity;
+        physics.player_speed = config.physics.player_speed;
+
+        // Les tronçons d'obstacles déjà générés gardent leur espacement
+        let min_distance = config.obstacles.min_distance_between_obstacles;
+        self.config.obstacles.min_distance_between_obstacles = min_distance;
+        self.obstacle_manager
+            .set_min_distance_between_obstacles(min_distance);
+
+        self.config.camera = config.camera.clone();
+        self.camera.set_config(config.camera.clone());
+
+        self.config.day_night = config.day_night.clone();
+
+        self.config.weather = config.weather.clone();
+        self.weather.set_config(config.weather.clone());
+    }
+
+    // Adapter la largeur de la vue à celle de la fenêtre : la caméra garde
+    // le même centre, décor et obstacles sont générés jusqu'aux nouveaux bords
+    pub fn set_screen_width(&mut self, screen_width: f32) {
+        if screen_width == self.screen_width {
+            return;
+        }
+
+        self.screen_width = screen_width;
+        self.camera.set_view_width(screen_width);
+        self.stream_scenery();
+    }
+
+    pub fn set_screen_shake(&mut self, enabled: bool) {
+        self.camera.set_shake_enabled(enabled);
+    }
+
+    pub fn set_transparent_foreground(&mut self, enabled: bool) {
+        self.transparent_foreground = enabled;
+    }
+
+    // Consommer le temps d'une frame par pas fixes de FIXED_TIME_STEP ;
+    // le reste est conservé pour la frame suivante et sert à l'interpolation.
+    // Pendant un arrêt sur image, le temps de la frame n'est pas simulé
+    pub fn update(&mut self, input: Input, frame_time: f32) {
+        let frame_time = self.update_effects(frame_time.min(MAX_FRAME_TIME));
+        self.accumulator += frame_time;
+
+        while self.accumulator >= FIXED_TIME_STEP {
+            self.step(input, FIXED_TIME_STEP);
+            self.accumulator -= FIXED_TIME_STEP;
+        }
+    }
+
+    // Faire avancer le tremblement, l'arrêt sur image et les particules
+    // (aussi après la fin de la partie) ; renvoie le temps restant à simuler
+    pub fn update_effects(&mut self, frame_time: f32) -> f32 {
+        self.camera.update_shake(frame_time);
+
+        let stopped = self.hit_stop.min(frame_time);
+        self.hit_stop -= stopped;
+
+        // Les particules restent figées pendant l'arrêt sur image
+        let remaining = frame_time - stopped;
+        self.particles.update(remaining);
+
+        // Après la fin de la partie, l'animation du joueur continue ici
+        if self.game_over {
+            self.player.update_animation(remaining, 0.0, true);
+        }
+        remaining
+    }
+
+    // Secouer la caméra et figer la simulation un court instant
+    fn impact(&mut self, trauma: f32, hit_stop: f32) {
+        self.camera.add_trauma(trauma);
+        self.hit_stop = self.hit_stop.max(hit_stop);
+    }
+
+    // Avancer la simulation de `delta_time` secondes
+    pub fn step(&mut self, input: Input, delta_time: f32) {
+        if self.game_over {
+            return;
+        }
+        self.run_time += delta_time;
+        let first_event = self.events.get_events().len();
+
+        // Mettre à jour le joueur (saut puis déplacement horizontal)
+        let was_jumping = self.player.is_jumping();
+        let fall_speed = self.player.get_velocity_y();
+        let physics = &self.config.physics;
+        self.player.set_wind(self.weather.get_player_wind());
+        self.player.update(
+            delta_time,
+            self.ground_level,
+            physics.jump_height,
+            physics.jump_charge_time_max,
+            physics.gravity,
+            input.space_pressed,
+        );
+        self.player
+            .move_horizontally(input.direction * physics.player_speed * delta_time);
+
+        if !was_jumping && self.player.is_jumping() {
+            let charge = self.player.get_jump_charge_time() / physics.jump_charge_time_max;
+            self.events.push(GameEvent::JumpStarted {
+                charge_percent: charge * 100.0,
+            });
+        }
+        if was_jumping && !self.player.is_jumping() {
+            self.events.push(GameEvent::Landed { fall_speed });
+
+            // Un atterrissage trop rapide secoue l'écran
+            if fall_speed > self.get_hard_landing_speed() {
+                self.impact(HARD_LANDING_TRAUMA, HARD_LANDING_HIT_STOP);
+            }
+        }
+
+        // La caméra suit le joueur
+        let (player_x, player_y) = self.player.get_position();
+        self.camera.update(
+            delta_time,
+            Vector2::new(player_x, player_y),
+            input.direction,
+        );
+        self.stream_scenery();
+        self.weather
+            .update(delta_time, self.run_time, &self.camera, self.screen_height);
+
+        // Mettre à jour les obstacles
+        for obstacle in self
+            .obstacle_manager
+            .update(&self.camera, self.ground_level)
+        {
+            let (x, y) = obstacle.get_position();
+            self.events.push(GameEvent::ObstacleSpawned {
+                obstacle_type: *obstacle.get_obstacle_type(),
+                position: Vector2::new(x, y),
+            });
+        }
+
+        let (player_width, player_height) = self.player.get_size();
+        for obstacle_type in self
+            .obstacle_manager
+            .clear_passed(player_x - player_width / 2.0)
+        {
+            self.events
+                .push(GameEvent::ObstacleCleared { obstacle_type });
+        }
+
+        // Mettre à jour le score (basé sur la distance parcourue)
+        self.score = ((self.get_player_world_x() - PLAYER_START_X) / 100.0) as i32;
+        while self.score >= self.next_milestone {
+            self.events.push(GameEvent::ScoreMilestone {
+                score: self.next_milestone,
+            });
+            self.next_milestone += SCORE_MILESTONE;
+        }
+
+        // Vérifier les collisions avec la position mondiale du joueur
+        if let Some(obstacle) = self.obstacle_manager.find_collision(
+            Vector2::new(player_x, player_y),
+            Vector2::new(player_width, player_height),
+        ) {
+            self.events.push(GameEvent::Collided {
+                obstacle_type: *obstacle.get_obstacle_type(),
+            });
+            self.events.push(GameEvent::RunEnded {
+                score: self.score,
+                seed: self.seed,
+            });
+            self.game_over = true;
+            self.impact(DEATH_TRAUMA, DEATH_HIT_STOP);
+        }
+
+        self.player
+            .update_animation(delta_time, input.direction, self.game_over);
+        self.spawn_particles(first_event);
+    }
+
+    // Effets de particules des événements produits pendant ce pas (à partir
+    // de l'index `first_event` de la file) et étincelles de la charge du saut
+    fn spawn_particles(&mut self, first_event: usize) {
+        let (x, y) = self.player.get_position();
+        let (_, height) = self.player.get_size();
+        let feet = Vector2::new(x, y + height);
+        let center = Vector2::new(x, y + height / 4.0);
+
+        for event in &self.events.get_events()[first_event..] {
+            match event {
+                GameEvent::Landed { fall_speed } => {
+                    let count = 6 + (fall_speed / 60.0) as usize;
+                    self.particles.burst(&LANDING_DUST, feet, count.min(24));
+                }
+                GameEvent::Collided { .. } => {
+                    self.particles.burst(&HIT_DEBRIS, center, 30);
+                }
+                _ => {}
+            }
+        }
+
+        let charging = self.player.is_charging_jump() && !self.game_over;
+        self.particles
+            .set_emitter_position(self.charge_emitter, center);
+        self.particles
+            .set_emitter_active(self.charge_emitter, charging);
+    }
+
+    // Événements produits depuis la dernière distribution
+    pub fn get_events(&self) -> &EventQueue {
+        &self.events
+    }
+
+    pub fn get_events_mut(&mut self) -> &mut EventQueue {
+        &mut self.events
+    }
+
+    // Position horizontale du joueur dans le monde
+    pub fn get_player_world_x(&self) -> f32 {
+        self.player.get_position().0
+    }
+
+    // Fraction du pas fixe écoulée depuis le dernier pas de simulation
+    pub fn get_interpolation_alpha(&self) -> f32 {
+        if self.game_over {
+            return 1.0;
+        }
+        self.accumulator / FIXED_TIME_STEP
+    }
+
+    // Caméra à sa position interpolée pour le rendu
+    pub fn get_render_camera(&self) -> Camera {
+        self.camera.interpolate(self.get_interpolation_alpha())
+    }
+
+    // Joueur à sa position interpolée pour le rendu
+    pub fn get_render_player(&self) -> Player {
+        self.player.interpolate(self.get_interpolation_alpha())
+    }
+
+    // Dessiner le monde (ciel, sol, décor, obstacles et joueur) aux positions
+    // interpolées ; l'interface est dessinée par l'appelant
+    pub fn draw(&self, d: &mut dyn Renderer) {
+        let camera = self.get_render_camera();
+        let player = self.get_render_player();
+
+        // Couleurs du biome au centre de la vue, mélangées dans les transitions,
+        // éclairées selon l'heure et assombries par le mauvais temps
+        let palette = palette_at(camera.get_position().x + self.screen_width / 2.0);
+        let time_of_day = self.get_time_of_day();
+        let light_filter = self.weather.get_light_filter();
+        let light = time_of_day.get_light().tint(light_filter);
+        let sky = palette.sky.tint(light_filter);
+        d.clear_background(sky);
+        time_of_day.draw_sky(d, sky, self.screen_width, self.screen_height, self.seed);
+
+        // Dessiner sol (jusqu'au bas de l'écran, même quand la caméra monte)
+        let ground_y = camera
+            .world_to_screen(Vector2::new(0.0, self.ground_level))
+            .y;
+        d.draw_rectangle(
+            0,
+            ground_y as i32,
+            self.screen_width as i32,
+            (self.screen_height - ground_y) as i32,
+            palette.ground.tint(light),
+        );
+
+        // Décor, obstacles, joueur, météo et particules passent par une même file triée par couche
+        let mut queue = RenderQueue::new();
+        self.scenery.queue_draw(&mut queue, &camera);
+        self.obstacle_manager.queue_draw(&mut queue, &camera);
+        self.weather.queue_draw(&mut queue, &camera);
+        self.particles.queue_draw(&mut queue, &camera);
+
+        let (player_x, player_y) = player.get_position();
+        let screen = camera.world_to_screen(Vector2::new(player_x, player_y));
+        queue.push(&player, screen);
+
+        for layer in [
+            LAYER_BACKGROUND,
+            LAYER_MIDDLE,
+            LAYER_GAMEPLAY,
+            LAYER_FOREGROUND,
+        ] {
+            queue.set_layer_light(layer, light);
+        }
+        if self.transparent_foreground {
+            queue.set_layer_alpha(LAYER_FOREGROUND, FOREGROUND_ALPHA);
+        }
+        queue.draw(d, self.screen_width);
+
+        // Éclair d'orage : tout l'écran s'illumine brièvement
+        let lightning = self.weather.get_lightning();
+        if lightning > 0.0 {
+            d.draw_rectangle(
+                0,
+                0,
+                self.screen_width as i32,
+                self.screen_height as i32,
+                Color::new(255, 255, 255, (lightning * 150.0) as u8),
+            );
+        }
+    }
+
+    // Heure de la journée, qui avance avec la durée de la partie ou la
+    // distance parcourue selon la configuration
+    pub fn get_time_of_day(&self) -> TimeOfDay {
+        let day_night = &self.config.day_night;
+        let progress = if !day_night.enabled {
+            0.0
+        } else {
+            match day_night.driver {
+                CycleDriver::Time => self.run_time / day_night.cycle_duration,
+                CycleDriver::Distance => {
+                    (self.get_player_world_x() - PLAYER_START_X) / day_night.cycle_length
+                }
+            }
+        };
+        TimeOfDay::new(day_night.start_time + progress)
+    }
+
+    // Biome dans lequel se trouve le joueur
+    pub fn get_biome(&self) -> &'static Biome {
+        let (current, next, t) = biome_blend_at(self.get_player_world_x());
+        if t < 0.5 {
+            current
+        } else {
+            next
+        }
+    }
+
+    pub fn get_particles(&self) -> &ParticleSystem {
+        &self.particles
+    }
+
+    pub fn get_weather(&self) -> &Weather {
+        &self.weather
+    }
+
+    pub fn get_screen_width(&self) -> f32 {
+        self.screen_width
+    }
+
+    pub fn get_screen_height(&self) -> f32 {
+        self.screen_height
+    }
+
+    pub fn get_ground_level(&self) -> f32 {
+        self.ground_level
+    }
+
+    pub fn get_jump_charge_time_max(&self) -> f32 {
+        self.config.physics.jump_charge_time_max
+    }
+
+    // Vitesse de chute (pixels/s) au-delà de laquelle l'atterrissage est
+    // brutal, voir HARD_LANDING_FACTOR
+    pub fn get_hard_landing_speed(&self) -> f32 {
+        let physics = &self.config.physics;
+        (2.0 * physics.gravity * physics.jump_height).sqrt() * HARD_LANDING_FACTOR
+    }
+
+    pub fn get_config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    pub fn get_player(&self) -> &Player {
+        &self.player
+    }
+
+    pub fn get_camera(&self) -> &Camera {
+        &self.camera
+    }
+
+    pub fn get_scenery(&self) -> &Scenery {
+        &self.scenery
+    }
+
+    pub fn get_obstacle_manager(&self) -> &ObstacleManager {
+        &self.obstacle_manager
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn get_score(&self) -> i32 {
+        self.score
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.game_over
+    }
+
+    pub fn is_hit_stopped(&self) -> bool {
+        self.hit_stop > 0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: u64 = 42;
+
+    // Avancer vers la droite en sautant régulièrement
+    fn scripted_input(frame: usize) -> Input {
+        Input::new(1.0, frame % 90 < 20)
+    }
+
+    // Jouer le script et renvoyer les obstacles apparus (x, y), triés
+    fn play(game: &mut GameState, frames: usize, frame_time: f32) -> Vec<(f32, f32)> {
+        let mut spawned = Vec::new();
+        for frame in 0..frames {
+            game.update(scripted_input(frame), frame_time);
+            for event in game.get_events_mut().take_events() {
+                if let GameEvent::ObstacleSpawned { position, .. } = event {
+                    spawned.push((position.x, position.y));
+                }
+            }
+        }
+        spawned.sort_by(|a, b| a.0.total_cmp(&b.0));
+        spawned
+    }
+
+    // Obstacles de `obstacles` dans la zone du monde vue aussi par `other`
+    fn seen_by_both(obstacles: &[(f32, f32)], other: &[(f32, f32)]) -> Vec<(f32, f32)> {
+        let from = obstacles[0].0.max(other[0].0);
+        let until = obstacles[obstacles.len() - 1]
+            .0
+            .min(other[other.len() - 1].0);
+        obstacles
+            .iter()
+            .copied()
+            .filter(|&(x, _)| (from..=until).contains(&x))
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_and_inputs_replay_identically() {
+        let mut first = GameState::new(GameConfig::default(), SEED);
+        let mut second = GameState::new(GameConfig::default(), SEED);
+        let first_obstacles = play(&mut first, 1200, 1.0 / 60.0);
+        let second_obstacles = play(&mut second, 1200, 1.0 / 60.0);
+
+        assert!(!first_obstacles.is_empty());
+        assert_eq!(first_obstacles, second_obstacles);
+        assert!(first.get_score() > 0);
+        assert_eq!(first.get_score(), second.get_score());
+        assert_eq!(first.is_game_over(), second.is_game_over());
+        assert_eq!(first.get_player_world_x(), second.get_player_world_x());
+    }
+
+    // Une partie relancée après un changement de largeur de fenêtre suit
+    // le même parcours qu'une partie neuve
+    #[test]
+    fn reset_after_resize_replays_identically() {
+        let mut fresh = GameState::new(GameConfig::default(), SEED);
+        let mut resized = GameState::new(GameConfig::default(), SEED + 1);
+        play(&mut resized, 300, 1.0 / 60.0);
+        resized.set_screen_width(1000.0);
+        resized.reset(SEED);
+
+        let fresh_obstacles = play(&mut fresh, 1200, 1.0 / 60.0);
+        let resized_obstacles = play(&mut resized, 1200, 1.0 / 60.0);
+
+        let common = seen_by_both(&fresh_obstacles, &resized_obstacles);
+        assert!(!common.is_empty());
+        assert_eq!(common, seen_by_both(&resized_obstacles, &fresh_obstacles));
+        assert_eq!(fresh.get_score(), resized.get_score());
+        assert_eq!(fresh.is_game_over(), resized.is_game_over());
+        assert_eq!(fresh.get_player_world_x(), resized.get_player_world_x());
+    }
+
+    // Hauteur atteinte par un saut chargé au maximum, en jouant des frames
+    // de `frame_time` secondes
+    fn full_jump_apex(frame_time: f32) -> f32 {
+        jump_apex(&mut GameState::new(GameConfig::default(), SEED), frame_time)
+    }
+
+    // Hauteur atteinte par un saut chargé au maximum depuis le sol, avec
+    // les réglages actuels de la partie
+    fn jump_apex(game: &mut GameState, frame_time: f32) -> f32 {
+        let ground_level = game.get_ground_level();
+        let charge_time = game.get_config().physics.jump_charge_time_max + 0.1;
+
+        let mut top = ground_level;
+        let mut time = 0.0;
+        while time < charge_time + 2.0 {
+            game.update(Input::new(0.0, time < charge_time), frame_time);
+            top = top.min(game.get_player().get_position().1);
+            time += frame_time;
+        }
+        ground_level - top
+    }
+
+    // La hauteur du saut ne dépend pas de la fréquence d'affichage
+    #[test]
+    fn jump_apex_matches_config_at_any_frame_rate() {
+        let jump_height = GameConfig::default().physics.jump_height;
+        for fps in [30.0, 60.0, 144.0] {
+            let apex = full_jump_apex(1.0 / fps);
+            assert!(
+                (apex - jump_height).abs() < 0.5,
+                "{} FPS : sommet à {} au lieu de {}",
+                fps,
+                apex,
+                jump_height
+            );
+        }
+    }
+
+    // Un saut chargé au maximum retombe assez vite pour secouer la caméra
+    // et figer la simulation
+    #[test]
+    fn full_jump_lands_hard() {
+        let mut game = GameState::new(GameConfig::default(), SEED);
+        let charge_time = game.get_config().physics.jump_charge_time_max + 0.1;
+        let frame_time = 1.0 / 60.0;
+
+        let mut time = 0.0;
+        while time < charge_time + 2.0 {
+            game.update(Input::new(0.0, time < charge_time), frame_time);
+            time += frame_time;
+            let landed = game
+                .get_events_mut()
+                .take_events()
+                .iter()
+                .any(|event| matches!(event, GameEvent::Landed { .. }));
+            if landed {
+                assert!(game.get_camera().get_trauma() > 0.0);
+                assert!(game.is_hit_stopped());
+                return;
+            }
+        }
+        panic!("le joueur n'est pas retombé");
+    }
+
+    // Une configuration rechargée en cours de partie change le saut suivant
+    #[test]
+    fn reload_changes_next_jump() {
+        let mut game = GameState::new(GameConfig::default(), SEED);
+        let apex = jump_apex(&mut game, 1.0 / 60.0);
+        assert!((apex - 200.0).abs() < 0.5);
+
+        let mut config = GameConfig::default();
+        config.physics.jump_height = 120.0;
+        game.apply_tuning(&config);
+        let apex = jump_apex(&mut game, 1.0 / 60.0);
+        assert!((apex - 120.0).abs() < 0.5, "sommet à {}", apex);
+    }
+}
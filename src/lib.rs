@@ -0,0 +1,29 @@
+// Cœur du jeu : la simulation et le dessin (via le trait Renderer) sont
+// indépendants de raylib, qui n'est utilisé que par l'exécutable
+// (feature "raylib", activée par défaut).
+pub mod animation;
+pub mod biome;
+pub mod camera;
+pub mod cli;
+pub mod color;
+pub mod config;
+pub mod daylight;
+pub mod drawable;
+pub mod events;
+pub mod game;
+pub mod math;
+pub mod obstacle;
+pub mod particles;
+pub mod player;
+pub mod render_queue;
+pub mod renderer;
+pub mod replay;
+pub mod rng;
+pub mod scenery;
+pub mod settings;
+pub mod software_renderer;
+pub mod sprite;
+pub mod state;
+pub mod utils;
+pub mod viewport;
+pub mod weather;
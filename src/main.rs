@@ -1,163 +1,318 @@
-mod player;
-mod scenery;
-mod drawable;
-mod utils;
-mod obstacle;
-
-use drawable::Drawable;
-use player::Player;
+use std::process;
+
 use raylib::prelude::*;
-use utils::{generate_world, handle_input};
-use obstacle::ObstacleManager;
+use vibegame::cli::{LaunchOptions, USAGE};
+use vibegame::color::Color;
+use vibegame::config::{ConfigWatcher, GameConfig};
+use vibegame::game::{GameState, Input};
+use vibegame::renderer::TexturedRenderer;
+use vibegame::replay::{run_headless, Replay, ReplayPlayer};
+use vibegame::rng::random_seed;
+use vibegame::settings::Settings;
+use vibegame::software_renderer::SoftwareRenderer;
+use vibegame::sprite::{SpriteAtlas, SpriteTextures};
+use vibegame::state::{FrameInput, StateId, StateMachine, Transition};
+use vibegame::utils::{handle_input, handle_menu_input};
+use vibegame::viewport::Viewport;
+
+// Fichier où est enregistrée la dernière partie jouée au clavier
+const LAST_RUN_REPLAY: &str = "last_run.replay";
+
+// Taille minimale de la fenêtre redimensionnable
+const MIN_WINDOW_WIDTH: i32 = 400;
+const MIN_WINDOW_HEIGHT: i32 = 300;
 
-const GAME_OVER_TEXT: &str = "Game Over!";
-const RESTART_TEXT: &str = "Appuyez sur ESPACE pour recommencer";
-const GAME_OVER_FONT_SIZE: i32 = 40;
-const RESTART_FONT_SIZE: i32 = 20;
+// Durée d'affichage du message de rechargement de la configuration (secondes)
+const CONFIG_MESSAGE_DURATION: f32 = 4.0;
+
+fn save_recording(recording: &Replay) {
+    if let Err(error) = recording.save(LAST_RUN_REPLAY) {
+        eprintln!("Impossible d'enregistrer {}: {}", LAST_RUN_REPLAY, error);
+    }
+}
 
 fn main() {
+    let options = LaunchOptions::parse(std::env::args().skip(1)).unwrap_or_else(|error| {
+        eprintln!("{}\n\n{}", error, USAGE);
+        process::exit(2);
+    });
+    if options.show_help {
+        println!("{}", USAGE);
+        return;
+    }
+
+    // Une graine fixée au lancement est réutilisée à chaque nouvelle partie
+    let launch_seed = options.seed;
+
+    // Réglages du jeu (valeurs par défaut si le fichier est absent) ; la taille
+    // passée en ligne de commande remplace celle du fichier
+    let config = GameConfig::load_with(&options.config_path, |config| {
+        config.window.width = options.width.unwrap_or(config.window.width);
+        config.window.height = options.height.unwrap_or(config.window.height);
+    })
+    .unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        process::exit(1);
+    });
+
+    // Feuilles de sprites du dossier des ressources ; en cas d'erreur, le
+    // jeu garde ses dessins procéduraux
+    let sprites = SpriteAtlas::load(&options.assets_path).unwrap_or_else(|error| {
+        eprintln!("Sprites ignorés : {}", error);
+        SpriteAtlas::new()
+    });
+
+    // Enregistrement à rejouer à la place du clavier (`--replay <fichier>`)
+    let replay = options.replay_path.as_ref().map(|path| {
+        Replay::load(path).unwrap_or_else(|error| {
+            eprintln!("{}: {}", path.display(), error);
+            process::exit(1);
+        })
+    });
+    if replay.is_some() && launch_seed.is_some() {
+        eprintln!("--seed ignoré : la graine du replay est utilisée");
+    }
+
+    // Mode sans fenêtre : rejouer l'enregistrement (ou simuler `--frames`
+    // frames sans entrée) et afficher le résultat
+    if options.headless {
+        let replay = replay.unwrap_or_else(|| {
+            let mut idle = Replay::new(launch_seed.unwrap_or_else(random_seed), config.clone());
+            let frame_time = 1.0 / config.window.target_fps as f32;
+            for _ in 0..options.frames.unwrap_or_default() {
+                idle.record(Input::default(), frame_time);
+            }
+            idle
+        });
+        let game = run_headless(&replay, config, options.frames);
+        let frame_count = replay.get_frames().len();
+        println!(
+            "Graine: {} | Frames: {} | Score: {} | Game over: {}",
+            replay.get_seed(),
+            options
+                .frames
+                .map_or(frame_count, |frames| frames.min(frame_count)),
+            game.get_score(),
+            game.is_game_over()
+        );
+
+        // Miniature de la dernière frame (`--screenshot <fichier.png>`)
+        if let Some(path) = &options.screenshot_path {
+            let window = &game.get_config().window;
+            let (width, height) = (window.width, window.height);
+            let viewport = Viewport::new(width as f32, height as f32, height as f32);
+            let mut renderer = SoftwareRenderer::new(width, height).unwrap_or_else(|error| {
+                eprintln!("Impossible de créer {}: {}", path.display(), error);
+                process::exit(1);
+            });
+            renderer.set_sprites(sprites);
+            game.draw(&mut viewport.scaled(&mut renderer));
+            viewport.draw_letterbox(&mut renderer);
+            if let Err(error) = renderer.save_png(path) {
+                eprintln!("Impossible d'enregistrer {}: {}", path.display(), error);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Un replay se déroule avec la configuration enregistrée (sauf au format
+    // 1, qui n'en a pas)
+    let mut replay_player = replay.map(ReplayPlayer::new);
+    let game_config = replay_player
+        .as_ref()
+        .and_then(ReplayPlayer::get_config)
+        .cloned()
+        .unwrap_or_else(|| config.clone());
+
     // Initialisation du jeu
-    let (mut rl, thread) = raylib::init()
-        .size(800, 600)
+    let mut builder = raylib::init();
+    builder
+        .size(config.window.width as i32, config.window.height as i32)
         .title("Vibe Game")
-        .build();
+        .resizable();
+    if options.fullscreen {
+        builder.fullscreen();
+    }
+    let (mut rl, thread) = builder.build();
+    rl.set_window_min_size(MIN_WINDOW_WIDTH, MIN_WINDOW_HEIGHT);
+
+    // Les textures ne peuvent être créées qu'une fois la fenêtre ouverte
+    let textures = SpriteTextures::load(&mut rl, &thread, sprites).unwrap_or_else(|error| {
+        eprintln!("Sprites ignorés : {}", error);
+        SpriteTextures::default()
+    });
+
+    // Le monde est dessiné à la hauteur logique de la configuration, quelle
+    // que soit la taille de la fenêtre
+    let logical_height = game_config.window.height as f32;
 
     // Définir le taux de rafraîchissement cible (FPS)
-    rl.set_target_fps(60);
-
-    let screen_width = 800.0;
-    let screen_height = 600.0;
-
-    // Constantes de jeu
-    let ground_level = screen_height - 50.0 - 60.0; // Hauteur du sol
-    let jump_height = 200.0; // Hauteur maximale du saut en pixels
-    let jump_charge_time_max = 0.5; // Temps maximal pour charger le saut (en secondes)
-    let gravity = 1200.0; // Force de gravité
-    let player_speed = 200.0; // Vitesse de déplacement horizontal
-
-    // Initialiser le joueur au milieu de l'écran et au niveau du sol
-    let mut player = Player::new(
-        Vector2::new(screen_width / 2.0, ground_level),
-        Vector2::new(40.0, 60.0),
-    );
+    rl.set_target_fps(config.window.target_fps);
 
-    // Décalage de la caméra (position du monde)
-    let mut camera_offset = 0.0;
+    // Échap sert à naviguer dans les menus, pas à fermer la fenêtre
+    rl.set_exit_key(None);
 
-    // Générer le monde
-    let scenery = generate_world(ground_level, screen_width);
+    // Initialiser la partie (joueur, décor, obstacles) ; un replay démarre
+    // directement en jeu, sinon sur l'écran choisi par `--mode`
+    let (seed, initial_state) = match &replay_player {
+        Some(replay_player) => (replay_player.get_seed(), StateId::Playing),
+        None => (
+            launch_seed.unwrap_or_else(random_seed),
+            options.mode.get_initial_state(),
+        ),
+    };
+    let mut machine = StateMachine::new(
+        GameState::new(game_config, seed),
+        Settings::default(),
+        launch_seed,
+        initial_state,
+    );
 
-    // Initialiser le gestionnaire d'obstacles
-    let mut obstacle_manager = ObstacleManager::new();
+    // Enregistrement de la partie en cours, avec sa configuration
+    let mut recording = Replay::new(seed, machine.get_game().get_config().clone());
 
-    // Variables de jeu
-    let mut game_over = false;
-    let mut score = 0;
+    // Rechargement à chaud des réglages (désactivé pendant un replay, qui doit
+    // se dérouler avec la configuration de départ) ; la fenêtre ouverte
+    // remplace celle du fichier
+    let mut config_watcher = match replay_player {
+        Some(_) => None,
+        None => Some(ConfigWatcher::new(
+            &options.config_path,
+            config.window.clone(),
+        )),
+    };
+    let mut config_message: Option<(String, Color, f32)> = None;
 
     // Boucle principale du jeu
     while !rl.window_should_close() {
-        // Récupérer le delta time
-        let delta_time = rl.get_frame_time();
+        // F11 bascule entre fenêtre et plein écran
+        if rl.is_key_pressed(KeyboardKey::KEY_F11) {
+            rl.toggle_fullscreen();
+        }
+
+        // La largeur de la vue suit celle de la fenêtre
+        let viewport = Viewport::new(
+            rl.get_screen_width() as f32,
+            rl.get_screen_height() as f32,
+            logical_height,
+        );
+        machine
+            .get_game_mut()
+            .set_screen_width(viewport.get_logical_width());
 
         // Obtenir le FPS
         let current_fps = rl.get_fps();
+        let is_playing = machine.get_state_id() == StateId::Playing;
 
-        if !game_over {
-            // Gérer les entrées utilisateur
-            let (direction, space_pressed) = handle_input(&rl);
-            
-            // Déplacer la caméra au lieu du joueur
-            camera_offset += direction * player_speed * delta_time;
-
-            // Mettre à jour le joueur
-            player.update(
-                delta_time,
-                ground_level,
-                jump_height,
-                jump_charge_time_max,
-                gravity,
-                space_pressed,
-            );
-
-            // Mettre à jour les obstacles
-            obstacle_manager.update(camera_offset, screen_width, ground_level);
-
-            // Vérifier les collisions avec la position mondiale du joueur
-            if obstacle_manager.check_collisions(
-                Vector2::new(screen_width / 2.0 + camera_offset, player.get_position().1),
-                Vector2::new(player.get_size().0, player.get_size().1)
-            ) {
-                game_over = true;
+        // La frame où la pause est demandée n'est pas simulée
+        let menu_input = handle_menu_input(&rl);
+        let is_simulated = is_playing && !menu_input.requests_pause();
+
+        // Entrées et delta time : lus au clavier, ou rejoués depuis le fichier
+        // pendant la partie (une fois l'enregistrement terminé, la partie reste figée),
+        // avec les rechargements de configuration enregistrés avant la frame
+        if let Some(replay_player) = replay_player.as_mut().filter(|_| is_simulated) {
+            for config in replay_player.take_reloads() {
+                machine.get_game_mut().apply_tuning(&config);
             }
+        }
+        let (play_input, delta_time) = match replay_player.as_mut() {
+            Some(replay_player) if is_simulated => match replay_player.next_frame() {
+                Some(frame) => (frame.input, frame.frame_time),
+                None => (Default::default(), 0.0),
+            },
+            _ => (handle_input(&rl), rl.get_frame_time()),
+        };
+        let input = FrameInput {
+            play: play_input,
+            menu: menu_input,
+        };
 
-            // Mettre à jour le score (basé sur la distance parcourue)
-            score = (camera_offset / 100.0) as i32;
+        // Enregistrer les entrées des frames simulées
+        if replay_player.is_none() && is_simulated {
+            recording.record(input.play, delta_time);
+        }
+
+        match machine.update(&input, delta_time) {
+            Transition::Quit => break,
+            Transition::NewRun => {
+                let game = machine.get_game();
+                recording = Replay::new(game.get_seed(), game.get_config().clone());
+            }
+            Transition::Switch(StateId::GameOver) if replay_player.is_none() => {
+                save_recording(&recording)
+            }
+            _ => {}
         }
 
-        // Vérifier le redémarrage avant de commencer le dessin
-        if game_over && rl.is_key_pressed(KeyboardKey::KEY_SPACE) {
-            game_over = false;
-            camera_offset = 0.0;
-            score = 0;
-            player = Player::new(
-                Vector2::new(screen_width / 2.0, ground_level),
-                Vector2::new(40.0, 60.0),
-            );
-            obstacle_manager = ObstacleManager::new();
+        // Appliquer les modifications du fichier de configuration ; une
+        // configuration invalide est refusée et les réglages actuels sont gardés
+        let frame_time = rl.get_frame_time();
+        if let Some(result) = config_watcher
+            .as_mut()
+            .and_then(|watcher| watcher.poll(frame_time))
+        {
+            config_message = Some(match result {
+                Ok(config) => {
+                    machine.get_game_mut().apply_tuning(&config);
+                    recording.record_reload(config);
+                    (
+                        "Configuration rechargée".to_string(),
+                        Color::WHITE,
+                        CONFIG_MESSAGE_DURATION,
+                    )
+                }
+                Err(error) => {
+                    eprintln!("{}", error);
+                    (
+                        format!("Configuration refusée : {}", error),
+                        Color::RED,
+                        CONFIG_MESSAGE_DURATION,
+                    )
+                }
+            });
         }
 
         // Draw
         let mut d = rl.begin_drawing(&thread);
-        d.clear_background(Color::SKYBLUE);
-
-        // Dessiner sol
-        d.draw_rectangle(
-            0,
-            ground_level as i32,
-            screen_width as i32,
-            (screen_height - ground_level) as i32,
-            Color::GREEN,
-        );
-
-        // Dessiner les éléments de décor (en fonction de la position de la caméra)
-        scenery.draw(&mut d, camera_offset, screen_width);
+        machine.draw(&viewport, &mut TexturedRenderer::new(&mut d, &textures));
 
-        // Dessiner les obstacles
-        obstacle_manager.draw(&mut d, camera_offset, screen_width);
+        // Afficher les informations
+        if machine.get_settings().show_fps {
+            d.draw_text(&format!("FPS: {}", current_fps), 10, 10, 20, Color::WHITE);
+        }
 
-        // Dessiner le joueur
-        player.draw(&mut d, player.get_position().0, screen_width);
+        if let Some((message, color, remaining)) = config_message.as_mut() {
+            // Seule la première ligne du message tient à l'écran
+            let first_line = message.lines().next().unwrap_or_default();
+            let window_height = viewport.get_window_height() as i32;
+            d.draw_text(first_line, 10, window_height - 30, 20, *color);
 
-        // Afficher les informations
-        d.draw_text(&format!("FPS: {}", current_fps), 10, 10, 20, Color::WHITE);
-        d.draw_text(
-            &format!("Score: {}", score),
-            10,
-            30,
-            20,
-            Color::WHITE,
-        );
+            *remaining -= frame_time;
+            if *remaining <= 0.0 {
+                config_message = None;
+            }
+        }
 
-        // Afficher la barre de charge du saut
-        player.draw_jump_charge(&mut d, jump_charge_time_max);
-
-        // Afficher l'écran de game over si nécessaire
-        if game_over {
-            d.draw_text(
-                GAME_OVER_TEXT,
-                (screen_width as i32 / 2) - 100,
-                (screen_height as i32 / 2) - GAME_OVER_FONT_SIZE / 2,
-                GAME_OVER_FONT_SIZE,
-                Color::RED,
-            );
-            
-            d.draw_text(
-                RESTART_TEXT,
-                (screen_width as i32 / 2) - 150,
-                (screen_height as i32 / 2) + 50,
-                RESTART_FONT_SIZE,
-                Color::WHITE,
-            );
+        if let Some(replay_player) = &replay_player {
+            let status = if replay_player.is_finished() {
+                "REPLAY (terminé)"
+            } else {
+                "REPLAY"
+            };
+            // Ancré en haut à droite de la fenêtre
+            let window_width = viewport.get_window_width() as i32;
+            let status_width = d.measure_text(status, 20);
+            d.draw_text(status, window_width - status_width - 10, 10, 20, Color::RED);
         }
     }
+
+    // Conserver la partie interrompue par la fermeture de la fenêtre
+    if replay_player.is_none()
+        && !recording.get_frames().is_empty()
+        && !machine.get_game().is_game_over()
+    {
+        save_recording(&recording);
+    }
 }
@@ -0,0 +1,19 @@
+// Vecteur 2D utilisé par la simulation (sans dépendance à raylib)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vector2 {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vector2 {
+    pub const fn new(x: f32, y: f32) -> Self {
+        Vector2 { x, y }
+    }
+}
+
+#[cfg(feature = "raylib")]
+impl From<Vector2> for raylib::ffi::Vector2 {
+    fn from(v: Vector2) -> Self {
+        raylib::ffi::Vector2 { x: v.x, y: v.y }
+    }
+}
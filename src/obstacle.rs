@@ -1,34 +1,57 @@
-use raylib::prelude::*;
+use std::collections::BTreeMap;
+
 use rand::Rng;
-use crate::drawable::Drawable;
+
+use crate::biome::pick_biome;
+use crate::camera::Camera;
+use crate::color::Color;
+use crate::config::ObstacleConfig;
+use crate::drawable::{Drawable, LAYER_GAMEPLAY};
+use crate::math::Vector2;
+use crate::render_queue::RenderQueue;
+use crate::renderer::Renderer;
+use crate::rng::create_chunk_rng;
+use crate::sprite::SpriteFrame;
 
 pub struct Obstacle {
     position: Vector2,
     size: Vector2,
     obstacle_type: ObstacleType,
+    cleared: bool, // Déjà dépassé par le joueur
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ObstacleType {
     Spike,
     Block,
     FloatingPlatform,
 }
 
-impl Obstacle {
-    pub fn new(position: Vector2, obstacle_type: ObstacleType) -> Self {
-        let size = match obstacle_type {
-            ObstacleType::Spike => Vector2::new(30.0, 30.0),
-            ObstacleType::Block => Vector2::new(40.0, 40.0),
-            ObstacleType::FloatingPlatform => Vector2::new(100.0, 20.0),
-        };
+impl ObstacleType {
+    // Nom de la feuille de sprites du type d'obstacle
+    pub fn get_sprite_name(&self) -> &'static str {
+        match self {
+            ObstacleType::Spike => "spike",
+            ObstacleType::Block => "block",
+            ObstacleType::FloatingPlatform => "platform",
+        }
+    }
+}
 
+impl Obstacle {
+    pub fn new(position: Vector2, obstacle_type: ObstacleType, size: Vector2) -> Self {
         Obstacle {
             position,
             size,
             obstacle_type,
+            cleared: false,
         }
     }
 
+    pub fn get_obstacle_type(&self) -> &ObstacleType {
+        &self.obstacle_type
+    }
+
     pub fn collides_with(&self, player_pos: Vector2, player_size: Vector2) -> bool {
         // Vérification simple de collision par boîtes englobantes
         let player_left = player_pos.x - player_size.x / 2.0;
@@ -49,19 +72,37 @@ impl Obstacle {
 }
 
 impl Drawable for Obstacle {
-    fn draw(&self, d: &mut RaylibDrawHandle, screen_x: f32, screen_width: f32) {
+    fn draw(&self, d: &mut dyn Renderer, screen_x: f32, screen_y: f32, screen_width: f32) {
         // Ne dessiner que si l'obstacle est visible à l'écran
         if screen_x < -self.size.x || screen_x > screen_width {
             return;
         }
 
+        // Image de la feuille de sprites si elle est chargée
+        let sprite = SpriteFrame {
+            sheet: self.obstacle_type.get_sprite_name(),
+            frame: 0,
+            animation: None,
+            flip_x: false,
+        };
+        if d.draw_sprite(
+            sprite,
+            screen_x as i32,
+            screen_y as i32,
+            self.size.x as i32,
+            self.size.y as i32,
+            Color::WHITE,
+        ) {
+            return;
+        }
+
         match self.obstacle_type {
             ObstacleType::Spike => {
                 // Dessiner un triangle pointu
                 d.draw_triangle(
-                    Vector2::new(screen_x, self.position.y + self.size.y),
-                    Vector2::new(screen_x + self.size.x, self.position.y + self.size.y),
-                    Vector2::new(screen_x + self.size.x / 2.0, self.position.y),
+                    Vector2::new(screen_x, screen_y + self.size.y),
+                    Vector2::new(screen_x + self.size.x, screen_y + self.size.y),
+                    Vector2::new(screen_x + self.size.x / 2.0, screen_y),
                     Color::RED,
                 );
             },
@@ -69,14 +110,14 @@ impl Drawable for Obstacle {
                 // Dessiner un bloc avec un contour
                 d.draw_rectangle(
                     screen_x as i32,
-                    self.position.y as i32,
+                    screen_y as i32,
                     self.size.x as i32,
                     self.size.y as i32,
                     Color::GRAY,
                 );
                 d.draw_rectangle_lines(
                     screen_x as i32,
-                    self.position.y as i32,
+                    screen_y as i32,
                     self.size.x as i32,
                     self.size.y as i32,
                     Color::DARKGRAY,
@@ -86,7 +127,7 @@ impl Drawable for Obstacle {
                 // Dessiner une plateforme flottante
                 d.draw_rectangle(
                     screen_x as i32,
-                    self.position.y as i32,
+                    screen_y as i32,
                     self.size.x as i32,
                     self.size.y as i32,
                     Color::DARKGREEN,
@@ -95,9 +136,9 @@ impl Drawable for Obstacle {
                 for i in 0..3 {
                     d.draw_line(
                         (screen_x + i as f32 * 30.0) as i32,
-                        self.position.y as i32,
+                        screen_y as i32,
                         (screen_x + i as f32 * 30.0) as i32,
-                        (self.position.y + self.size.y) as i32,
+                        (screen_y + self.size.y) as i32,
                         Color::GREEN,
                     );
                 }
@@ -114,77 +155,277 @@ impl Drawable for Obstacle {
     }
 
     fn get_layer(&self) -> i32 {
-        1
+        LAYER_GAMEPLAY
+    }
+
+    // Devant le décor du premier plan, derrière le joueur
+    fn get_depth(&self) -> f32 {
+        1.0
     }
 }
 
+// Largeur d'un tronçon d'obstacles (pixels du monde)
+const OBSTACLE_CHUNK_WIDTH: f32 = 1024.0;
+
+// Tronçons gardés de part et d'autre de la vue
+const STREAM_MARGIN_CHUNKS: i64 = 1;
+
+// Flux aléatoire des obstacles, distinct de ceux des couches de décor
+const OBSTACLE_RNG_STREAM: u64 = 16;
+
+// Pas d'obstacle à moins de cette distance de la position de départ du joueur
+const START_CLEARANCE: f32 = 400.0;
+
+fn chunk_index(x: f32) -> i64 {
+    (x / OBSTACLE_CHUNK_WIDTH).floor() as i64
+}
+
+// Espacement d'un tronçon, fixé à sa première génération pour qu'il soit
+// régénéré à l'identique, et zone où il peut placer des obstacles (loin des
+// tronçons voisins générés avant lui avec un autre espacement)
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ChunkLayout {
+    spacing: f32,
+    from: f32,
+    until: f32,
+}
+
+// Obstacles générés par tronçon à partir de la graine et de l'index du
+// tronçon : le parcours est le même quel que soit le sens de la marche
 pub struct ObstacleManager {
-    obstacles: Vec<Obstacle>,
-    last_spawn_position: f32,
-    min_distance_between_obstacles: f32,
+    chunks: BTreeMap<i64, Vec<Obstacle>>,
+    layouts: BTreeMap<i64, ChunkLayout>, // Tous les tronçons déjà générés, même oubliés
+    seed: u64,
+    start_x: f32,       // Position de départ du joueur, gardée libre
+    cleared_until: f32, // Obstacles finissant avant ce point déjà signalés comme dépassés
+    spawned: Option<(i64, i64)>, // Tronçons (premier, dernier) dont l'apparition est déjà signalée
+    config: ObstacleConfig, // Tailles et espacement des prochains tronçons
 }
 
 impl ObstacleManager {
-    pub fn new() -> Self {
+    pub fn new(config: ObstacleConfig, seed: u64, start_x: f32) -> Self {
         ObstacleManager {
-            obstacles: Vec::new(),
-            last_spawn_position: 0.0,
-            min_distance_between_obstacles: 300.0,
+            chunks: BTreeMap::new(),
+            layouts: BTreeMap::new(),
+            seed,
+            start_x,
+            cleared_until: start_x,
+            spawned: None,
+            config,
         }
     }
 
-    pub fn update(&mut self, camera_offset: f32, screen_width: f32, ground_level: f32) {
-        let mut rng = rand::thread_rng();
-        
-        // Supprimer les obstacles qui sont trop loin derrière la caméra
-        self.obstacles.retain(|obstacle| {
-            obstacle.position.x + obstacle.size.x > camera_offset - screen_width / 2.0
+    // Espacement des tronçons générés pour la première fois à partir de
+    // maintenant ; ceux déjà générés gardent le leur, même régénérés
+    pub fn set_min_distance_between_obstacles(&mut self, distance: f32) {
+        self.config.min_distance_between_obstacles = distance;
+    }
+
+    // Générer les tronçons qui approchent de la vue et oublier ceux qu'elle a
+    // quittés ; renvoie les obstacles qui apparaissent pour la première fois
+    // (un tronçon régénéré en revenant en arrière n'est pas signalé à nouveau)
+    pub fn update(&mut self, camera: &Camera, ground_level: f32) -> Vec<&Obstacle> {
+        let left = camera.get_position().x;
+        let first = chunk_index(left) - STREAM_MARGIN_CHUNKS;
+        let last = chunk_index(left + camera.get_view_width()) + STREAM_MARGIN_CHUNKS;
+
+        self.chunks.retain(|chunk, _| (first..=last).contains(chunk));
+
+        let mut created = Vec::new();
+        for chunk in first..=last {
+            if !self.chunks.contains_key(&chunk) {
+                let layout = match self.layouts.get(&chunk) {
+                    Some(layout) => *layout,
+                    None => self.new_layout(chunk),
+                };
+                self.layouts.insert(chunk, layout);
+                let obstacles = self.generate_chunk(chunk, layout, ground_level);
+                self.chunks.insert(chunk, obstacles);
+                if !self
+                    .spawned
+                    .is_some_and(|(from, until)| (from..=until).contains(&chunk))
+                {
+                    created.push(chunk);
+                }
+            }
+        }
+
+        // La vue avance sans sauts : les tronçons signalés restent contigus
+        self.spawned = Some(match self.spawned {
+            Some((from, until)) => (from.min(first), until.max(last)),
+            None => (first, last),
         });
 
-        // Vérifier si nous devons générer un nouvel obstacle
-        let current_position = camera_offset + screen_width;
-        if current_position - self.last_spawn_position >= self.min_distance_between_obstacles {
-            // Décider aléatoirement quel type d'obstacle créer
-            let obstacle_type = match rng.random_range(0..3) {
-                0 => ObstacleType::Spike,
-                1 => ObstacleType::Block,
-                _ => ObstacleType::FloatingPlatform,
-            };
+        created
+            .into_iter()
+            .flat_map(|chunk| &self.chunks[&chunk])
+            .collect()
+    }
 
-            // Déterminer la taille de l'obstacle
-            let size = match obstacle_type {
-                ObstacleType::Spike => Vector2::new(30.0, 30.0),
-                ObstacleType::Block => Vector2::new(40.0, 40.0),
-                ObstacleType::FloatingPlatform => Vector2::new(100.0, 20.0),
-            };
+    // Disposition d'un tronçon jamais généré : espacement actuel, en gardant
+    // cet écart avec l'obstacle le plus proche de chaque voisin déjà généré
+    fn new_layout(&self, chunk: i64) -> ChunkLayout {
+        let spacing = self.config.min_distance_between_obstacles;
+        let start = chunk as f32 * OBSTACLE_CHUNK_WIDTH;
+        let end = start + OBSTACLE_CHUNK_WIDTH;
+
+        let mut layout = ChunkLayout {
+            spacing,
+            from: start,
+            until: end,
+        };
+        if let Some(left) = self.layouts.get(&(chunk - 1)) {
+            let last_slot = ((start / left.spacing).ceil() - 1.0) * left.spacing;
+            layout.from = layout.from.max(last_slot + spacing);
+        }
+        if let Some(right) = self.layouts.get(&(chunk + 1)) {
+            let first_slot = (end / right.spacing).ceil() * right.spacing;
+            layout.until = layout.until.min(first_slot - spacing);
+        }
+        layout
+    }
+
+    // Obstacles d'un tronçon : un tous les `spacing` pixels depuis l'origine
+    // du monde, de type et de hauteur tirés du générateur du tronçon selon le
+    // biome traversé
+    fn generate_chunk(&self, chunk: i64, layout: ChunkLayout, ground_level: f32) -> Vec<Obstacle> {
+        let mut rng = create_chunk_rng(self.seed, OBSTACLE_RNG_STREAM, chunk);
+        let spacing = layout.spacing;
+        let start = chunk as f32 * OBSTACLE_CHUNK_WIDTH;
+        let end = start + OBSTACLE_CHUNK_WIDTH;
+
+        let mut obstacles = Vec::new();
+        let mut slot = (start / spacing).ceil();
+        let mut x = slot * spacing;
+        while x < end {
+            // Décider aléatoirement quel type d'obstacle créer, selon le
+            // mélange d'obstacles du biome
+            let obstacle_type = pick_biome(x, &mut rng).pick_obstacle(&mut rng);
 
             // Position Y en fonction du type d'obstacle
             let y = match obstacle_type {
                 // Les obstacles au sol doivent être alignés avec le niveau du sol du joueur
                 ObstacleType::Spike | ObstacleType::Block => ground_level,
                 // Les plateformes flottantes doivent être positionnées par rapport au même niveau
-                ObstacleType::FloatingPlatform => ground_level - rng.random_range(60.0..100.0),
+                ObstacleType::FloatingPlatform => {
+                    ground_level
+                        - rng.random_range(
+                            self.config.platform_height_min..self.config.platform_height_max,
+                        )
+                }
             };
 
-            // Créer le nouvel obstacle
-            let obstacle = Obstacle::new(
-                Vector2::new(current_position, y),
-                obstacle_type,
-            );
+            // Laisser le joueur démarrer sans obstacle à proximité, ni trop
+            // près d'un voisin (les tirages sont faits quand même pour ne pas
+            // décaler les suivants)
+            if (x - self.start_x).abs() >= START_CLEARANCE
+                && (layout.from..=layout.until).contains(&x)
+            {
+                let size = self.config.size_of(&obstacle_type);
+                let mut obstacle = Obstacle::new(Vector2::new(x, y), obstacle_type, size);
+                obstacle.cleared = x + size.x < self.cleared_until;
+                obstacles.push(obstacle);
+            }
 
-            self.obstacles.push(obstacle);
-            self.last_spawn_position = current_position;
+            slot += 1.0;
+            x = slot * spacing;
         }
+
+        obstacles
     }
 
-    pub fn draw(&self, d: &mut RaylibDrawHandle, camera_offset: f32, screen_width: f32) {
-        for obstacle in &self.obstacles {
-            let screen_x = obstacle.position.x - camera_offset;
-            obstacle.draw(d, screen_x, screen_width);
+    fn obstacles(&self) -> impl Iterator<Item = &Obstacle> {
+        self.chunks.values().flatten()
+    }
+
+    // Marquer les obstacles que le joueur vient de dépasser (bord gauche du
+    // joueur à `player_left`) et renvoyer leurs types ; un obstacle n'est
+    // signalé qu'une fois, même si le joueur revient en arrière
+    pub fn clear_passed(&mut self, player_left: f32) -> Vec<ObstacleType> {
+        let mut cleared = Vec::new();
+        for obstacle in self.chunks.values_mut().flatten() {
+            if !obstacle.cleared && obstacle.position.x + obstacle.size.x < player_left {
+                obstacle.cleared = true;
+                cleared.push(obstacle.obstacle_type);
+            }
         }
+        self.cleared_until = self.cleared_until.max(player_left);
+        cleared
     }
 
-    pub fn check_collisions(&self, player_pos: Vector2, player_size: Vector2) -> bool {
-        self.obstacles.iter().any(|obstacle| obstacle.collides_with(player_pos, player_size))
+    // Ajouter à la file de rendu les obstacles visibles
+    pub fn queue_draw<'a>(&'a self, queue: &mut RenderQueue<'a>, camera: &Camera) {
+        for obstacle in self.obstacles() {
+            let screen = camera.world_to_screen(obstacle.position);
+            if camera.is_visible(screen.x, obstacle.size.x) {
+                queue.push(obstacle, screen);
+            }
+        }
     }
-} 
\ No newline at end of file
+
+    // Premier obstacle touché par le joueur
+    pub fn find_collision(&self, player_pos: Vector2, player_size: Vector2) -> Option<&Obstacle> {
+        self.obstacles().find(|obstacle| obstacle.collides_with(player_pos, player_size))
+    }
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CameraConfig;
+
+    const GROUND_LEVEL: f32 = 490.0;
+
+    // Obstacles (x, y, sorte) de chaque tronçon chargé
+    type Snapshot = BTreeMap<i64, Vec<(f32, f32, ObstacleType)>>;
+
+    // Amener la vue à `x` (bord gauche) et relever les tronçons chargés
+    fn visit(manager: &mut ObstacleManager, x: f32, seen: &mut Snapshot) {
+        let camera = Camera::new(CameraConfig::default(), 800.0, 600.0, Vector2::new(x + 400.0, 0.0));
+        manager.update(&camera, GROUND_LEVEL);
+        for (chunk, obstacles) in &manager.chunks {
+            let obstacles = obstacles
+                .iter()
+                .map(|o| (o.position.x, o.position.y, o.obstacle_type))
+                .collect::<Vec<_>>();
+            let first = seen.entry(*chunk).or_insert_with(|| obstacles.clone());
+            assert_eq!(first, &obstacles, "tronçon {} régénéré différemment", chunk);
+        }
+    }
+
+    // Déplacer la vue de `from` à `to` par pas de 100 pixels
+    fn walk(manager: &mut ObstacleManager, seen: &mut Snapshot, from: i32, to: i32) {
+        let step = if to > from { 100 } else { -100 };
+        let mut x = from;
+        while x != to {
+            visit(manager, x as f32, seen);
+            x += step;
+        }
+    }
+
+    // Aller à droite sur plusieurs tronçons, revenir à gauche au-delà du
+    // départ, puis repartir à droite : chaque tronçon est identique à chaque
+    // passage, même après un changement d'espacement en route
+    #[test]
+    fn chunks_are_stable_whichever_way_the_player_moves() {
+        let mut manager = ObstacleManager::new(ObstacleConfig::default(), 42, 400.0);
+        let mut seen = Snapshot::new();
+        walk(&mut manager, &mut seen, 0, 8000);
+        walk(&mut manager, &mut seen, 8000, -4000);
+        manager.set_min_distance_between_obstacles(450.0);
+        walk(&mut manager, &mut seen, -4000, 12000);
+        walk(&mut manager, &mut seen, 12000, -8000);
+
+        assert!(seen.len() > 15);
+        assert!(seen.values().flatten().any(|o| o.2 == ObstacleType::Spike));
+        assert!(seen.values().flatten().any(|o| o.2 == ObstacleType::Block));
+        assert!(seen.values().flatten().any(|o| o.2 == ObstacleType::FloatingPlatform));
+
+        // Les tronçons générés après le changement respectent le nouvel
+        // espacement, y compris contre leurs voisins
+        let mut xs: Vec<f32> = seen.values().flatten().map(|o| o.0).collect();
+        xs.sort_by(f32::total_cmp);
+        assert!(xs.windows(2).all(|pair| pair[1] - pair[0] >= 300.0));
+        assert!(seen[&10].windows(2).all(|pair| pair[1].0 - pair[0].0 == 450.0));
+    }
+}
@@ -0,0 +1,380 @@
+use std::f32::consts::PI;
+
+use rand::Rng;
+
+use crate::camera::Camera;
+use crate::color::Color;
+use crate::drawable::{Drawable, LAYER_GAMEPLAY};
+use crate::math::Vector2;
+use crate::render_queue::RenderQueue;
+use crate::renderer::Renderer;
+use crate::rng::{create_chunk_rng, GameRng};
+
+// Nombre maximal de particules vivantes ; au-delà, les nouvelles sont ignorées
+const MAX_PARTICLES: usize = 512;
+
+// Flux aléatoire des particules, distinct de ceux du monde et de la météo
+const PARTICLE_RNG_STREAM: u64 = 64;
+
+// Description d'un effet : comment naissent, bougent et s'effacent ses particules
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParticleEffect {
+    pub speed: (f32, f32),    // Vitesse initiale, minimum et maximum (pixels/s)
+    pub angle: (f32, f32),    // Direction initiale en degrés (0 = droite, -90 = haut)
+    pub spread: Vector2,      // Zone d'apparition autour du point d'émission
+    pub gravity: f32,         // Accélération vers le bas (pixels/s²)
+    pub lifetime: (f32, f32), // Durée de vie, minimum et maximum (secondes)
+    pub start_color: Color,
+    pub end_color: Color, // Couleur en fin de vie (l'opacité aussi est interpolée)
+    pub start_size: f32,
+    pub end_size: f32,
+    pub rate: f32,  // Particules par seconde des émetteurs continus
+    pub depth: f32, // Profondeur dans la couche du joueur (2.0)
+}
+
+// Poussière soulevée à l'atterrissage, derrière le joueur
+pub const LANDING_DUST: ParticleEffect = ParticleEffect {
+    speed: (40.0, 120.0),
+    angle: (-170.0, -10.0),
+    spread: Vector2::new(20.0, 2.0),
+    gravity: 200.0,
+    lifetime: (0.3, 0.6),
+    start_color: Color::new(200, 180, 150, 200),
+    end_color: Color::new(200, 180, 150, 0),
+    start_size: 6.0,
+    end_size: 2.0,
+    rate: 0.0,
+    depth: 1.5,
+};
+
+// Étincelles qui montent autour du joueur pendant la charge du saut
+pub const CHARGE_SPARKLES: ParticleEffect = ParticleEffect {
+    speed: (30.0, 70.0),
+    angle: (-120.0, -60.0),
+    spread: Vector2::new(50.0, 60.0),
+    gravity: -40.0,
+    lifetime: (0.3, 0.5),
+    start_color: Color::new(255, 240, 120, 255),
+    end_color: Color::new(255, 160, 40, 0),
+    start_size: 5.0,
+    end_size: 1.0,
+    rate: 40.0,
+    depth: 2.5,
+};
+
+// Débris projetés quand le joueur heurte un obstacle
+pub const HIT_DEBRIS: ParticleEffect = ParticleEffect {
+    speed: (120.0, 320.0),
+    angle: (-180.0, 0.0),
+    spread: Vector2::new(10.0, 10.0),
+    gravity: 900.0,
+    lifetime: (0.6, 1.0),
+    start_color: Color::new(230, 41, 55, 255),
+    end_color: Color::new(80, 80, 80, 0),
+    start_size: 7.0,
+    end_size: 3.0,
+    rate: 0.0,
+    depth: 2.5,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Particle {
+    position: Vector2, // Centre de la particule dans le monde
+    velocity: Vector2,
+    age: f32,
+    lifetime: f32,
+    effect: ParticleEffect,
+}
+
+impl Particle {
+    // Avancement dans la vie de la particule, de 0.0 à 1.0
+    fn get_progress(&self) -> f32 {
+        (self.age / self.lifetime).min(1.0)
+    }
+
+    fn get_current_size(&self) -> f32 {
+        let t = self.get_progress();
+        self.effect.start_size + (self.effect.end_size - self.effect.start_size) * t
+    }
+
+    fn is_alive(&self) -> bool {
+        self.age < self.lifetime
+    }
+}
+
+impl Drawable for Particle {
+    // `screen_x`, `screen_y` : position du centre à l'écran
+    fn draw(&self, d: &mut dyn Renderer, screen_x: f32, screen_y: f32, _screen_width: f32) {
+        let size = self.get_current_size().max(1.0);
+        let color = self
+            .effect
+            .start_color
+            .lerp(self.effect.end_color, self.get_progress());
+        d.draw_rectangle(
+            (screen_x - size / 2.0) as i32,
+            (screen_y - size / 2.0) as i32,
+            size as i32,
+            size as i32,
+            color,
+        );
+    }
+
+    fn get_position(&self) -> (f32, f32) {
+        (self.position.x, self.position.y)
+    }
+
+    fn get_size(&self) -> (f32, f32) {
+        let size = self.get_current_size();
+        (size, size)
+    }
+
+    fn get_layer(&self) -> i32 {
+        LAYER_GAMEPLAY
+    }
+
+    fn get_depth(&self) -> f32 {
+        self.effect.depth
+    }
+}
+
+// Émetteur continu : crée `effect.rate` particules par seconde tant qu'il est actif
+#[derive(Clone, Copy, Debug)]
+pub struct Emitter {
+    effect: ParticleEffect,
+    position: Vector2,
+    active: bool,
+    spawn_budget: f32, // Particules dues pas encore créées
+}
+
+// Identifiant d'un émetteur continu dans un ParticleSystem
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EmitterId(usize);
+
+// Particules de la partie, dans un réservoir de taille fixe : une particule
+// morte laisse sa place à la suivante, sans allocation en cours de partie
+pub struct ParticleSystem {
+    particles: Vec<Particle>, // Les `alive_count` premières sont vivantes
+    alive_count: usize,
+    emitters: Vec<Emitter>,
+    rng: GameRng,
+}
+
+impl ParticleSystem {
+    pub fn new(seed: u64) -> Self {
+        ParticleSystem {
+            particles: Vec::with_capacity(MAX_PARTICLES),
+            alive_count: 0,
+            emitters: Vec::new(),
+            rng: create_chunk_rng(seed, PARTICLE_RNG_STREAM, 0),
+        }
+    }
+
+    // Créer d'un coup `count` particules de l'effet autour de `position`
+    pub fn burst(&mut self, effect: &ParticleEffect, position: Vector2, count: usize) {
+        for _ in 0..count {
+            self.spawn(effect, position);
+        }
+    }
+
+    // Ajouter un émetteur continu, inactif jusqu'à set_emitter_active
+    pub fn add_emitter(&mut self, effect: ParticleEffect) -> EmitterId {
+        self.emitters.push(Emitter {
+            effect,
+            position: Vector2::default(),
+            active: false,
+            spawn_budget: 0.0,
+        });
+        EmitterId(self.emitters.len() - 1)
+    }
+
+    pub fn set_emitter_position(&mut self, id: EmitterId, position: Vector2) {
+        self.emitters[id.0].position = position;
+    }
+
+    pub fn set_emitter_active(&mut self, id: EmitterId, active: bool) {
+        let emitter = &mut self.emitters[id.0];
+        if !active {
+            emitter.spawn_budget = 0.0;
+        }
+        emitter.active = active;
+    }
+
+    fn spawn(&mut self, effect: &ParticleEffect, position: Vector2) {
+        if self.alive_count >= MAX_PARTICLES {
+            return;
+        }
+
+        let rng = &mut self.rng;
+        let angle = rng.random_range(effect.angle.0..=effect.angle.1) * PI / 180.0;
+        let speed = rng.random_range(effect.speed.0..=effect.speed.1);
+        let offset = Vector2::new(
+            (rng.random::<f32>() - 0.5) * effect.spread.x,
+            (rng.random::<f32>() - 0.5) * effect.spread.y,
+        );
+        let particle = Particle {
+            position: Vector2::new(position.x + offset.x, position.y + offset.y),
+            velocity: Vector2::new(angle.cos() * speed, angle.sin() * speed),
+            age: 0.0,
+            lifetime: rng.random_range(effect.lifetime.0..=effect.lifetime.1),
+            effect: *effect,
+        };
+
+        // Réutiliser la place d'une particule morte
+        if self.alive_count < self.particles.len() {
+            self.particles[self.alive_count] = particle;
+        } else {
+            self.particles.push(particle);
+        }
+        self.alive_count += 1;
+    }
+
+    // Faire vivre les particules et les émetteurs pendant `delta_time` secondes
+    pub fn update(&mut self, delta_time: f32) {
+        for index in 0..self.emitters.len() {
+            let emitter = &mut self.emitters[index];
+            if !emitter.active {
+                continue;
+            }
+            emitter.spawn_budget += emitter.effect.rate * delta_time;
+            let count = emitter.spawn_budget as usize;
+            emitter.spawn_budget -= count as f32;
+
+            let (effect, position) = (emitter.effect, emitter.position);
+            self.burst(&effect, position, count);
+        }
+
+        let mut index = 0;
+        while index < self.alive_count {
+            let particle = &mut self.particles[index];
+            particle.velocity.y += particle.effect.gravity * delta_time;
+            particle.position.x += particle.velocity.x * delta_time;
+            particle.position.y += particle.velocity.y * delta_time;
+            particle.age += delta_time;
+
+            // Une particule morte prend la place de la dernière vivante
+            if particle.is_alive() {
+                index += 1;
+            } else {
+                self.alive_count -= 1;
+                self.particles.swap(index, self.alive_count);
+            }
+        }
+    }
+
+    // Ajouter à la file de rendu les particules visibles
+    pub fn queue_draw<'a>(&'a self, queue: &mut RenderQueue<'a>, camera: &Camera) {
+        for particle in &self.particles[..self.alive_count] {
+            let screen = camera.world_to_screen(particle.position);
+            let size = particle.get_current_size();
+            if camera.is_visible(screen.x - size / 2.0, size) {
+                queue.push(particle, screen);
+            }
+        }
+    }
+
+    pub fn get_particle_count(&self) -> usize {
+        self.alive_count
+    }
+
+    // Supprimer toutes les particules (les émetteurs sont gardés)
+    pub fn clear(&mut self) {
+        self.alive_count = 0;
+        for emitter in &mut self.emitters {
+            emitter.spawn_budget = 0.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEP: f32 = 1.0 / 120.0;
+
+    fn step(system: &mut ParticleSystem, seconds: f32) {
+        for _ in 0..(seconds / STEP).round() as usize {
+            system.update(STEP);
+        }
+    }
+
+    // Position et vitesse des particules vivantes
+    fn snapshot(system: &ParticleSystem) -> Vec<(f32, f32, f32, f32)> {
+        system.particles[..system.alive_count]
+            .iter()
+            .map(|particle| {
+                let (x, y) = particle.get_position();
+                (x, y, particle.velocity.x, particle.velocity.y)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn dead_particles_are_reused() {
+        let mut system = ParticleSystem::new(1);
+        let capacity = system.particles.capacity();
+
+        system.burst(&HIT_DEBRIS, Vector2::new(0.0, 0.0), 40);
+        assert_eq!(system.get_particle_count(), 40);
+
+        // Toutes mortes : leurs places servent aux suivantes
+        step(&mut system, HIT_DEBRIS.lifetime.1 + 0.1);
+        assert_eq!(system.get_particle_count(), 0);
+        system.burst(&LANDING_DUST, Vector2::new(0.0, 0.0), 30);
+        assert_eq!(system.get_particle_count(), 30);
+        assert_eq!(system.particles.len(), 40);
+        assert_eq!(system.particles.capacity(), capacity);
+
+        // Une partie seulement est morte : seules les vivantes sont gardées
+        step(&mut system, 0.1);
+        system.burst(&HIT_DEBRIS, Vector2::new(0.0, 0.0), 10);
+        step(&mut system, LANDING_DUST.lifetime.1 + 0.05);
+        assert_eq!(system.get_particle_count(), 10);
+        assert!(system.particles[..10]
+            .iter()
+            .all(|particle| particle.effect == HIT_DEBRIS));
+        assert_eq!(system.particles.len(), 40);
+    }
+
+    #[test]
+    fn pool_never_grows_past_capacity() {
+        let mut system = ParticleSystem::new(1);
+        let capacity = system.particles.capacity();
+        let emitter = system.add_emitter(ParticleEffect {
+            rate: 10_000.0,
+            ..CHARGE_SPARKLES
+        });
+        system.set_emitter_active(emitter, true);
+
+        system.burst(&HIT_DEBRIS, Vector2::new(0.0, 0.0), MAX_PARTICLES * 2);
+        assert_eq!(system.get_particle_count(), MAX_PARTICLES);
+        for _ in 0..120 {
+            system.update(STEP);
+            system.burst(&LANDING_DUST, Vector2::new(0.0, 0.0), 100);
+            assert!(system.get_particle_count() <= MAX_PARTICLES);
+            assert!(system.particles.len() <= MAX_PARTICLES);
+        }
+        assert_eq!(system.particles.capacity(), capacity);
+    }
+
+    #[test]
+    fn same_seed_emits_the_same_particles() {
+        let run = |seed| {
+            let mut system = ParticleSystem::new(seed);
+            let emitter = system.add_emitter(CHARGE_SPARKLES);
+            system.set_emitter_position(emitter, Vector2::new(400.0, 460.0));
+            system.set_emitter_active(emitter, true);
+            step(&mut system, 0.3);
+            system.burst(&LANDING_DUST, Vector2::new(420.0, 490.0), 12);
+            step(&mut system, 0.2);
+            system.set_emitter_active(emitter, false);
+            system.burst(&HIT_DEBRIS, Vector2::new(450.0, 470.0), 20);
+            step(&mut system, 0.2);
+            snapshot(&system)
+        };
+
+        let first = run(7);
+        assert!(!first.is_empty());
+        assert_eq!(first, run(7));
+        assert_ne!(first, run(8));
+    }
+}
@@ -1,24 +1,121 @@
-use raylib::prelude::*;
-use crate::drawable::Drawable;
+use crate::animation::{Animation, AnimationInput, AnimationState};
+use crate::color::Color;
+use crate::drawable::{Drawable, LAYER_GAMEPLAY};
+use crate::math::Vector2;
+use crate::renderer::Renderer;
+use crate::sprite::SpriteFrame;
 
+// Feuille de sprites du joueur ; sans animation décrite dans la feuille,
+// image 0 au sol et image 1 en l'air ou pendant la charge du saut
+pub const PLAYER_SPRITE: &str = "player";
+
+// Décalages (pixels) du dessin procédural pour une image d'animation ; un
+// décalage négatif remonte l'élément
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Pose {
+    body_y: i32,       // Torse, tête et bras (accroupi, respiration)
+    left_leg_y: i32,   // Jambe levée
+    right_leg_y: i32,
+    left_arm_y: i32,
+    right_arm_y: i32,
+    look_x: i32,       // Direction du regard
+    mouth_open: bool,
+    knocked_out: bool, // Yeux en croix
+}
+
+impl Pose {
+    fn new(state: AnimationState, frame: usize, height: i32) -> Self {
+        match state {
+            AnimationState::Idle => Pose {
+                // Respiration lente
+                body_y: [0, 1, 1, 0][frame],
+                ..Pose::default()
+            },
+            AnimationState::RunLeft | AnimationState::RunRight => {
+                // Jambes en alternance, bras opposés aux jambes
+                let stride = [-6, 0, 0, 0][frame];
+                let other_stride = [0, 0, -6, 0][frame];
+                let look_x = if state == AnimationState::RunLeft { -2 } else { 2 };
+                Pose {
+                    body_y: [1, 0, 1, 0][frame],
+                    left_leg_y: stride,
+                    right_leg_y: other_stride,
+                    left_arm_y: other_stride,
+                    right_arm_y: stride,
+                    look_x,
+                    ..Pose::default()
+                }
+            }
+            AnimationState::Charging => Pose {
+                // Accroupi, tremble sous l'effort
+                body_y: 4 + frame as i32,
+                left_arm_y: 2,
+                right_arm_y: 2,
+                mouth_open: true,
+                ..Pose::default()
+            },
+            AnimationState::Rising => Pose {
+                // Bras levés, jambes repliées
+                left_leg_y: -4,
+                right_leg_y: -4,
+                left_arm_y: -height / 4,
+                right_arm_y: -height / 4,
+                mouth_open: true,
+                ..Pose::default()
+            },
+            AnimationState::Falling => Pose {
+                left_arm_y: -height / 8,
+                right_arm_y: -height / 8,
+                mouth_open: true,
+                ..Pose::default()
+            },
+            AnimationState::Landing => Pose {
+                // Écrasé à la réception, puis se redresse
+                body_y: [6, 4, 2][frame],
+                left_arm_y: 2,
+                right_arm_y: 2,
+                ..Pose::default()
+            },
+            AnimationState::Dying => Pose {
+                // S'affaisse
+                body_y: 2 + frame as i32 * 3,
+                left_arm_y: 4,
+                right_arm_y: 4,
+                mouth_open: true,
+                knocked_out: true,
+                ..Pose::default()
+            },
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct Player {
-    position: Vector2,      // Position X (fixe), Y (variable pendant le saut)
+    position: Vector2,      // Position dans le monde
+    previous_position: Vector2, // Position au pas de simulation précédent
     size: Vector2,         // Taille du joueur
     velocity_y: f32,       // Vitesse verticale pour le saut
+    drift_x: f32,          // Vitesse horizontale due au vent pendant le saut
+    wind: f32,             // Accélération horizontale du vent (pixels/s²), subie en l'air
     is_jumping: bool,      // État de saut
     is_charging_jump: bool, // État de charge du saut
     jump_charge_time: f32,  // Temps actuel de charge du saut
+    animation: Animation,   // Animation en cours, choisie d'après l'état
 }
 
 impl Player {
     pub fn new(position: Vector2, size: Vector2) -> Self {
         Player {
             position,
+            previous_position: position,
             size,
             velocity_y: 0.0,
+            drift_x: 0.0,
+            wind: 0.0,
             is_jumping: false,
             is_charging_jump: false,
             jump_charge_time: 0.0,
+            animation: Animation::new(),
         }
     }
 
@@ -31,6 +128,8 @@ impl Player {
         gravity: f32,
         is_space_pressed: bool,
     ) {
+        self.previous_position = self.position;
+
         // Gestion du saut avec charge
         if !self.is_jumping {
             if is_space_pressed {
@@ -49,20 +148,80 @@ impl Player {
         }
 
         // Appliquer la gravité et mettre à jour la position verticale
+        // (intégration exacte pour une accélération constante : l'apogée du saut
+        // ne dépend pas du pas de temps)
         if self.is_jumping {
+            self.position.y += self.velocity_y * delta_time + 0.5 * gravity * delta_time * delta_time;
             self.velocity_y += gravity * delta_time;
-            self.position.y += self.velocity_y * delta_time;
+
+            // Le vent pousse le joueur tant qu'il est en l'air
+            self.position.x += self.drift_x * delta_time + 0.5 * self.wind * delta_time * delta_time;
+            self.drift_x += self.wind * delta_time;
 
             // Vérifier si le joueur est retombé au sol
             if self.position.y >= ground_level {
                 self.position.y = ground_level;
                 self.velocity_y = 0.0;
+                self.drift_x = 0.0;
                 self.is_jumping = false;
             }
         }
     }
 
-    pub fn draw_jump_charge(&self, d: &mut RaylibDrawHandle, jump_charge_time_max: f32) {
+    // Faire avancer l'animation après update() et move_horizontally() ;
+    // `direction` est celle du déplacement horizontal (-1.0, 0.0 ou 1.0),
+    // `is_dead` vrai après un choc fatal
+    pub fn update_animation(&mut self, delta_time: f32, direction: f32, is_dead: bool) {
+        let input = AnimationInput {
+            is_jumping: self.is_jumping,
+            is_charging: self.is_charging_jump,
+            velocity_y: self.velocity_y,
+            direction,
+            is_dead,
+        };
+        self.animation.update(delta_time, &input);
+    }
+
+    pub fn get_animation(&self) -> &Animation {
+        &self.animation
+    }
+
+    // Vent appliqué aux prochains appels à update()
+    pub fn set_wind(&mut self, wind: f32) {
+        self.wind = wind;
+    }
+
+    pub fn is_jumping(&self) -> bool {
+        self.is_jumping
+    }
+
+    pub fn is_charging_jump(&self) -> bool {
+        self.is_charging_jump
+    }
+
+    pub fn get_velocity_y(&self) -> f32 {
+        self.velocity_y
+    }
+
+    pub fn get_jump_charge_time(&self) -> f32 {
+        self.jump_charge_time
+    }
+
+    // Déplacement horizontal dans le monde, après update() pour le même pas
+    pub fn move_horizontally(&mut self, distance: f32) {
+        self.position.x += distance;
+    }
+
+    // Copie du joueur à une position interpolée entre les deux derniers pas
+    // de simulation (alpha = 0 : pas précédent, alpha = 1 : pas courant)
+    pub fn interpolate(&self, alpha: f32) -> Player {
+        let mut player = self.clone();
+        player.position.x = self.previous_position.x + (self.position.x - self.previous_position.x) * alpha;
+        player.position.y = self.previous_position.y + (self.position.y - self.previous_position.y) * alpha;
+        player
+    }
+
+    pub fn draw_jump_charge(&self, d: &mut dyn Renderer, jump_charge_time_max: f32) {
         if self.is_charging_jump {
             let charge_percent = self.jump_charge_time / jump_charge_time_max;
             let bar_width = 200;
@@ -82,9 +241,9 @@ impl Player {
 }
 
 impl Drawable for Player {
-    fn draw(&self, d: &mut RaylibDrawHandle, screen_x: f32, _screen_width: f32) {
+    fn draw(&self, d: &mut dyn Renderer, screen_x: f32, screen_y: f32, _screen_width: f32) {
         let x = screen_x as i32;
-        let y = self.position.y as i32;
+        let y = screen_y as i32;
         let width = self.size.x as i32;
         let height = self.size.y as i32;
 
@@ -106,11 +265,34 @@ impl Drawable for Player {
             Color::new(0, 0, 0, 50)
         );
 
+        // Image de la feuille de sprites si elle est chargée, étirée sur la
+        // zone du dessin procédural (bras et tête compris)
+        let sprite = SpriteFrame {
+            sheet: PLAYER_SPRITE,
+            frame: if self.is_jumping || self.is_charging_jump { 1 } else { 0 },
+            animation: Some(self.animation.get_sprite_animation()),
+            flip_x: false,
+        };
+        if d.draw_sprite(
+            sprite,
+            x - width * 2 / 3,
+            y - height / 2,
+            width * 4 / 3,
+            height * 3 / 2,
+            Color::WHITE
+        ) {
+            return;
+        }
+
+        // Posture de l'image d'animation en cours
+        let pose = Pose::new(self.animation.get_state(), self.animation.get_frame(), height);
+        let body_y = y + pose.body_y;
+
         // Jambes
         // Pantalon (gauche)
         d.draw_rectangle(
             x - width / 3 - 2,
-            y + height * 1/2,
+            y + height / 2 + pose.left_leg_y,
             width / 3,
             height / 2,
             pants_color
@@ -118,7 +300,7 @@ impl Drawable for Player {
         // Pantalon (droite)
         d.draw_rectangle(
             x + 2,
-            y + height * 1/2,
+            y + height / 2 + pose.right_leg_y,
             width / 3,
             height / 2,
             pants_color
@@ -127,14 +309,14 @@ impl Drawable for Player {
         // Chaussures
         d.draw_rectangle(
             x - width / 3 - 4,
-            y + height - 12,
+            y + height - 12 + pose.left_leg_y,
             width / 3 + 2,
             12,
             shoes_color
         );
         d.draw_rectangle(
             x + 2,
-            y + height - 12,
+            y + height - 12 + pose.right_leg_y,
             width / 3 + 2,
             12,
             shoes_color
@@ -143,7 +325,7 @@ impl Drawable for Player {
         // Corps (torse)
         d.draw_rectangle(
             x - width / 2,
-            y - height / 4,
+            body_y - height / 4,
             width,
             height * 3/4,
             body_color
@@ -152,7 +334,7 @@ impl Drawable for Player {
         // Contour du corps
         d.draw_rectangle_lines(
             x - width / 2,
-            y - height / 4,
+            body_y - height / 4,
             width,
             height * 3/4,
             body_outline
@@ -161,7 +343,7 @@ impl Drawable for Player {
         // Tête
         d.draw_rectangle(
             x - width / 2 + 2,
-            y - height / 4 - height / 4,
+            body_y - height / 4 - height / 4,
             width - 4,
             height / 4,
             skin_color
@@ -170,7 +352,7 @@ impl Drawable for Player {
         // Contour de tête
         d.draw_rectangle_lines(
             x - width / 2 + 2,
-            y - height / 4 - height / 4,
+            body_y - height / 4 - height / 4,
             width - 4,
             height / 4,
             skin_outline
@@ -179,56 +361,65 @@ impl Drawable for Player {
         // Cheveux
         d.draw_rectangle(
             x - width / 2 + 2,
-            y - height / 4 - height / 4,
+            body_y - height / 4 - height / 4,
             width - 4,
             height / 8,
             hair_color
         );
 
         // Yeux
-        d.draw_rectangle(
-            x - width / 5,
-            y - height / 4 - height / 6,
-            width / 10,
-            height / 16,
-            Color::WHITE
-        );
-        d.draw_rectangle(
-            x + width / 10,
-            y - height / 4 - height / 6,
-            width / 10,
-            height / 16,
-            Color::WHITE
-        );
+        if pose.knocked_out {
+            // Yeux en croix après un choc fatal
+            for eye_x in [x - width / 5, x + width / 10] {
+                let eye_y = body_y - height / 4 - height / 6;
+                d.draw_line(eye_x, eye_y, eye_x + width / 10, eye_y + height / 16, Color::BLACK);
+                d.draw_line(eye_x, eye_y + height / 16, eye_x + width / 10, eye_y, Color::BLACK);
+            }
+        } else {
+            d.draw_rectangle(
+                x - width / 5,
+                body_y - height / 4 - height / 6,
+                width / 10,
+                height / 16,
+                Color::WHITE
+            );
+            d.draw_rectangle(
+                x + width / 10,
+                body_y - height / 4 - height / 6,
+                width / 10,
+                height / 16,
+                Color::WHITE
+            );
 
-        // Pupilles
-        d.draw_rectangle(
-            x - width / 5 + 2,
-            y - height / 4 - height / 6 + 2,
-            width / 15,
-            height / 20,
-            Color::BLACK
-        );
-        d.draw_rectangle(
-            x + width / 10 + 2,
-            y - height / 4 - height / 6 + 2,
-            width / 15,
-            height / 20,
-            Color::BLACK
-        );
+            // Pupilles, tournées dans le sens de la course
+            d.draw_rectangle(
+                x - width / 5 + 2 + pose.look_x,
+                body_y - height / 4 - height / 6 + 2,
+                width / 15,
+                height / 20,
+                Color::BLACK
+            );
+            d.draw_rectangle(
+                x + width / 10 + 2 + pose.look_x,
+                body_y - height / 4 - height / 6 + 2,
+                width / 15,
+                height / 20,
+                Color::BLACK
+            );
+        }
 
         // Bouche
-        if self.is_jumping || self.is_charging_jump {
-            // Bouche ouverte (forme de "o") pendant le saut ou la charge
+        if pose.mouth_open {
+            // Bouche ouverte (forme de "o") pendant le saut, la charge ou la chute
             d.draw_circle(
                 x,
-                y - height / 4 - height / 16,
+                body_y - height / 4 - height / 16,
                 (width / 10) as f32,
                 Color::new(200, 100, 100, 255)
             );
             d.draw_circle(
                 x,
-                y - height / 4 - height / 16,
+                body_y - height / 4 - height / 16,
                 (width / 15) as f32,
                 Color::new(100, 10, 10, 255)
             );
@@ -236,9 +427,9 @@ impl Drawable for Player {
             // Bouche fermée (sourire simple)
             d.draw_line(
                 x - width / 6,
-                y - height / 4 - height / 16,
+                body_y - height / 4 - height / 16,
                 x + width / 6,
-                y - height / 4 - height / 16,
+                body_y - height / 4 - height / 16,
                 Color::new(200, 100, 100, 255)
             );
         }
@@ -246,14 +437,14 @@ impl Drawable for Player {
         // Bras (gauche)
         d.draw_rectangle(
             x - width / 2 - width / 6,
-            y,
+            body_y + pose.left_arm_y,
             width / 6,
             height / 2,
             body_color
         );
         d.draw_rectangle_lines(
             x - width / 2 - width / 6,
-            y,
+            body_y + pose.left_arm_y,
             width / 6,
             height / 2,
             body_outline
@@ -262,14 +453,14 @@ impl Drawable for Player {
         // Bras (droite)
         d.draw_rectangle(
             x + width / 2,
-            y,
+            body_y + pose.right_arm_y,
             width / 6,
             height / 2,
             body_color
         );
         d.draw_rectangle_lines(
             x + width / 2,
-            y,
+            body_y + pose.right_arm_y,
             width / 6,
             height / 2,
             body_outline
@@ -278,14 +469,14 @@ impl Drawable for Player {
         // Mains
         d.draw_rectangle(
             x - width / 2 - width / 6 - 4,
-            y + height / 2 - 8,
+            body_y + pose.left_arm_y + height / 2 - 8,
             width / 6 + 2,
             8,
             skin_color
         );
         d.draw_rectangle(
             x + width / 2 + 2,
-            y + height / 2 - 8,
+            body_y + pose.right_arm_y + height / 2 - 8,
             width / 6 + 2,
             8,
             skin_color
@@ -294,9 +485,9 @@ impl Drawable for Player {
         // Détails du corps - ceinture
         d.draw_line(
             x - width / 2,
-            y + height / 4,
+            body_y + height / 4,
             x + width / 2,
-            y + height / 4,
+            body_y + height / 4,
             Color::new(200, 180, 60, 255)
         );
 
@@ -304,7 +495,7 @@ impl Drawable for Player {
         for i in 0..3 {
             d.draw_rectangle(
                 x - 2,
-                y - height / 8 + i * (height / 6),
+                body_y - height / 8 + i * (height / 6),
                 4,
                 4,
                 Color::new(220, 220, 220, 255)
@@ -321,6 +512,85 @@ impl Drawable for Player {
     }
 
     fn get_layer(&self) -> i32 {
-        2
+        LAYER_GAMEPLAY
+    }
+
+    // Devant le décor et les obstacles de sa couche
+    fn get_depth(&self) -> f32 {
+        2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{DrawCommand, RecordingRenderer};
+
+    fn rectangle(x: i32, y: i32, width: i32, height: i32, color: Color) -> DrawCommand {
+        DrawCommand::Rectangle { x, y, width, height, color }
+    }
+
+    fn rectangle_lines(x: i32, y: i32, width: i32, height: i32, color: Color) -> DrawCommand {
+        DrawCommand::RectangleLines { x, y, width, height, color }
     }
-}
\ No newline at end of file
+
+    // Joueur au repos : ombre, tentative de sprite refusée par le
+    // RecordingRenderer, puis dessin procédural complet
+    #[test]
+    fn draw_idle_player_snapshot() {
+        let player = Player::new(Vector2::new(100.0, 200.0), Vector2::new(40.0, 60.0));
+        let mut renderer = RecordingRenderer::new();
+        player.draw(&mut renderer, 100.0, 200.0, 800.0);
+
+        let body = Color::new(30, 90, 150, 255);
+        let body_outline = Color::new(20, 60, 100, 255);
+        let skin = Color::new(255, 213, 170, 255);
+        let pants = Color::new(40, 40, 120, 255);
+        let shoes = Color::new(160, 40, 40, 255);
+        let eye_white = Color::new(255, 255, 255, 255);
+        let button = Color::new(220, 220, 220, 255);
+        let expected = vec![
+            DrawCommand::Circle { center_x: 100, center_y: 260, radius: 20.0, color: Color::new(0, 0, 0, 50) },
+            DrawCommand::Sprite {
+                sheet: PLAYER_SPRITE,
+                frame: 0,
+                flip_x: false,
+                x: 74,
+                y: 170,
+                width: 53,
+                height: 90,
+                tint: Color::WHITE,
+            },
+            // Jambes et chaussures
+            rectangle(85, 230, 13, 30, pants),
+            rectangle(102, 230, 13, 30, pants),
+            rectangle(83, 248, 15, 12, shoes),
+            rectangle(102, 248, 15, 12, shoes),
+            // Corps
+            rectangle(80, 185, 40, 45, body),
+            rectangle_lines(80, 185, 40, 45, body_outline),
+            // Tête, cheveux, yeux et bouche
+            rectangle(82, 170, 36, 15, skin),
+            rectangle_lines(82, 170, 36, 15, Color::new(200, 160, 130, 255)),
+            rectangle(82, 170, 36, 7, Color::new(80, 50, 20, 255)),
+            rectangle(92, 175, 4, 3, eye_white),
+            rectangle(104, 175, 4, 3, eye_white),
+            rectangle(94, 177, 2, 3, Color::new(0, 0, 0, 255)),
+            rectangle(106, 177, 2, 3, Color::new(0, 0, 0, 255)),
+            DrawCommand::Line { start_x: 94, start_y: 182, end_x: 106, end_y: 182, color: Color::new(200, 100, 100, 255) },
+            // Bras et mains
+            rectangle(74, 200, 6, 30, body),
+            rectangle_lines(74, 200, 6, 30, body_outline),
+            rectangle(120, 200, 6, 30, body),
+            rectangle_lines(120, 200, 6, 30, body_outline),
+            rectangle(70, 222, 8, 8, skin),
+            rectangle(122, 222, 8, 8, skin),
+            // Ceinture et boutons
+            DrawCommand::Line { start_x: 80, start_y: 215, end_x: 120, end_y: 215, color: Color::new(200, 180, 60, 255) },
+            rectangle(98, 193, 4, 4, button),
+            rectangle(98, 203, 4, 4, button),
+            rectangle(98, 213, 4, 4, button),
+        ];
+        assert_eq!(renderer.get_commands(), expected.as_slice());
+    }
+}
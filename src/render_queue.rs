@@ -0,0 +1,200 @@
+use crate::color::Color;
+use crate::drawable::Drawable;
+use crate::math::Vector2;
+use crate::renderer::{Renderer, TintedRenderer, TranslucentRenderer};
+
+// Élément en attente de dessin, déjà placé à l'écran
+struct QueuedDrawable<'a> {
+    drawable: &'a dyn Drawable,
+    screen_position: Vector2,
+    layer: i32,
+    depth: f32,
+}
+
+// File de rendu d'une frame : rassemble tous les Drawable (décor, obstacles,
+// joueur) puis les dessine triés par couche et par profondeur ; à égalité,
+// l'ordre d'ajout est conservé
+#[derive(Default)]
+pub struct RenderQueue<'a> {
+    items: Vec<QueuedDrawable<'a>>,
+    layer_alphas: Vec<(i32, f32)>, // Opacité des couches partiellement transparentes
+    layer_lights: Vec<(i32, Color)>, // Éclairage des couches teintées
+}
+
+impl<'a> RenderQueue<'a> {
+    pub fn new() -> Self {
+        RenderQueue {
+            items: Vec::new(),
+            layer_alphas: Vec::new(),
+            layer_lights: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, drawable: &'a dyn Drawable, screen_position: Vector2) {
+        self.items.push(QueuedDrawable {
+            drawable,
+            screen_position,
+            layer: drawable.get_layer(),
+            depth: drawable.get_depth(),
+        });
+    }
+
+    // Dessiner toute une couche avec une opacité de `alpha` (0.0 à 1.0)
+    pub fn set_layer_alpha(&mut self, layer: i32, alpha: f32) {
+        self.layer_alphas.retain(|(other, _)| *other != layer);
+        self.layer_alphas.push((layer, alpha.clamp(0.0, 1.0)));
+    }
+
+    // Éclairer toute une couche par `light` (heure de la journée)
+    pub fn set_layer_light(&mut self, layer: i32, light: Color) {
+        self.layer_lights.retain(|(other, _)| *other != layer);
+        self.layer_lights.push((layer, light));
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    // Dessiner les éléments dans l'ordre et vider la file
+    pub fn draw(&mut self, d: &mut dyn Renderer, screen_width: f32) {
+        self.items
+            .sort_by(|a, b| a.layer.cmp(&b.layer).then(a.depth.total_cmp(&b.depth)));
+
+        for item in self.items.drain(..) {
+            let alpha = self
+                .layer_alphas
+                .iter()
+                .find(|(layer, _)| *layer == item.layer)
+                .map_or(1.0, |(_, alpha)| *alpha);
+            let light = self
+                .layer_lights
+                .iter()
+                .find(|(layer, _)| *layer == item.layer)
+                .map_or(Color::WHITE, |(_, light)| *light);
+
+            if light != Color::WHITE {
+                let mut tinted = TintedRenderer::new(d, light);
+                draw_item(&mut tinted, &item, alpha, screen_width);
+            } else {
+                draw_item(d, &item, alpha, screen_width);
+            }
+        }
+    }
+}
+
+fn draw_item(d: &mut dyn Renderer, item: &QueuedDrawable, alpha: f32, screen_width: f32) {
+    let position = item.screen_position;
+    if alpha < 1.0 {
+        let mut translucent = TranslucentRenderer::new(d, alpha);
+        item.drawable
+            .draw(&mut translucent, position.x, position.y, screen_width);
+    } else {
+        item.drawable.draw(d, position.x, position.y, screen_width);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawable::{LAYER_BACKGROUND, LAYER_FOREGROUND, LAYER_GAMEPLAY};
+    use crate::renderer::{DrawCommand, RecordingRenderer};
+
+    // Élément de test qui dessine un rectangle portant son identifiant
+    struct Marker {
+        id: i32,
+        layer: i32,
+        depth: f32,
+    }
+
+    impl Drawable for Marker {
+        fn draw(&self, d: &mut dyn Renderer, _screen_x: f32, _screen_y: f32, _screen_width: f32) {
+            d.draw_rectangle(self.id, 0, 1, 1, Color::WHITE);
+        }
+
+        fn get_position(&self) -> (f32, f32) {
+            (0.0, 0.0)
+        }
+
+        fn get_size(&self) -> (f32, f32) {
+            (1.0, 1.0)
+        }
+
+        fn get_layer(&self) -> i32 {
+            self.layer
+        }
+
+        fn get_depth(&self) -> f32 {
+            self.depth
+        }
+    }
+
+    #[test]
+    fn draws_by_layer_then_depth_keeping_insertion_order_on_ties() {
+        let markers = [
+            Marker {
+                id: 0,
+                layer: LAYER_FOREGROUND,
+                depth: 0.0,
+            },
+            Marker {
+                id: 1,
+                layer: LAYER_GAMEPLAY,
+                depth: 1.0,
+            },
+            Marker {
+                id: 2,
+                layer: LAYER_BACKGROUND,
+                depth: 5.0,
+            },
+            Marker {
+                id: 3,
+                layer: LAYER_GAMEPLAY,
+                depth: 0.0,
+            },
+            Marker {
+                id: 4,
+                layer: LAYER_GAMEPLAY,
+                depth: 1.0,
+            },
+            Marker {
+                id: 5,
+                layer: LAYER_BACKGROUND,
+                depth: -1.0,
+            },
+            Marker {
+                id: 6,
+                layer: LAYER_GAMEPLAY,
+                depth: 0.0,
+            },
+            Marker {
+                id: 7,
+                layer: LAYER_GAMEPLAY,
+                depth: 1.0,
+            },
+        ];
+
+        let mut queue = RenderQueue::new();
+        for marker in &markers {
+            queue.push(marker, Vector2::new(0.0, 0.0));
+        }
+        assert_eq!(queue.len(), markers.len());
+
+        let mut recorder = RecordingRenderer::new();
+        queue.draw(&mut recorder, 800.0);
+        assert!(queue.is_empty());
+
+        let order: Vec<i32> = recorder
+            .get_commands()
+            .iter()
+            .map(|command| match command {
+                DrawCommand::Rectangle { x, .. } => *x,
+                other => panic!("commande inattendue : {:?}", other),
+            })
+            .collect();
+        assert_eq!(order, vec![5, 2, 3, 6, 1, 4, 7, 0]);
+    }
+}
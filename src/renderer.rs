@@ -0,0 +1,483 @@
+use crate::color::Color;
+use crate::math::Vector2;
+use crate::sprite::SpriteFrame;
+
+// Primitives de dessin utilisées par les éléments du jeu, indépendantes de la
+// bibliothèque graphique (mêmes conventions que raylib : coordonnées écran en
+// pixels, origine en haut à gauche)
+pub trait Renderer {
+    fn clear_background(&mut self, color: Color);
+    fn draw_rectangle(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color);
+    fn draw_rectangle_lines(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color);
+    fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color);
+    fn draw_circle(&mut self, center_x: i32, center_y: i32, radius: f32, color: Color);
+    fn draw_line(&mut self, start_x: i32, start_y: i32, end_x: i32, end_y: i32, color: Color);
+    fn draw_text(&mut self, text: &str, x: i32, y: i32, font_size: i32, color: Color);
+    // Largeur en pixels d'un texte dessiné par draw_text
+    fn measure_text(&self, text: &str, font_size: i32) -> i32;
+
+    // Dessiner une image d'une feuille de sprites étirée sur le rectangle,
+    // ses couleurs multipliées par `tint` ; renvoie false si ce Renderer n'a
+    // pas la feuille, l'appelant dessine alors sa version procédurale
+    fn draw_sprite(
+        &mut self,
+        _sprite: SpriteFrame,
+        _x: i32,
+        _y: i32,
+        _width: i32,
+        _height: i32,
+        _tint: Color,
+    ) -> bool {
+        false
+    }
+}
+
+// Dessiner un texte centré horizontalement sur `center_x`
+pub fn draw_text_centered(
+    d: &mut dyn Renderer,
+    text: &str,
+    center_x: i32,
+    y: i32,
+    font_size: i32,
+    color: Color,
+) {
+    let width = d.measure_text(text, font_size);
+    d.draw_text(text, center_x - width / 2, y, font_size, color);
+}
+
+// Dessine à travers un autre Renderer en rendant toutes les couleurs
+// partiellement transparentes
+pub struct TranslucentRenderer<'a> {
+    target: &'a mut dyn Renderer,
+    alpha: f32, // Opacité appliquée, de 0.0 à 1.0
+}
+
+impl<'a> TranslucentRenderer<'a> {
+    pub fn new(target: &'a mut dyn Renderer, alpha: f32) -> Self {
+        TranslucentRenderer { target, alpha }
+    }
+
+    fn fade(&self, color: Color) -> Color {
+        let alpha = (color.a as f32 * self.alpha).round() as u8;
+        Color::new(color.r, color.g, color.b, alpha)
+    }
+}
+
+impl Renderer for TranslucentRenderer<'_> {
+    fn clear_background(&mut self, color: Color) {
+        self.target.clear_background(color);
+    }
+
+    fn draw_rectangle(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        let color = self.fade(color);
+        self.target.draw_rectangle(x, y, width, height, color);
+    }
+
+    fn draw_rectangle_lines(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        let color = self.fade(color);
+        self.target.draw_rectangle_lines(x, y, width, height, color);
+    }
+
+    fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        let color = self.fade(color);
+        self.target.draw_triangle(v1, v2, v3, color);
+    }
+
+    fn draw_circle(&mut self, center_x: i32, center_y: i32, radius: f32, color: Color) {
+        let color = self.fade(color);
+        self.target.draw_circle(center_x, center_y, radius, color);
+    }
+
+    fn draw_line(&mut self, start_x: i32, start_y: i32, end_x: i32, end_y: i32, color: Color) {
+        let color = self.fade(color);
+        self.target.draw_line(start_x, start_y, end_x, end_y, color);
+    }
+
+    fn draw_text(&mut self, text: &str, x: i32, y: i32, font_size: i32, color: Color) {
+        let color = self.fade(color);
+        self.target.draw_text(text, x, y, font_size, color);
+    }
+
+    fn measure_text(&self, text: &str, font_size: i32) -> i32 {
+        self.target.measure_text(text, font_size)
+    }
+
+    fn draw_sprite(
+        &mut self,
+        sprite: SpriteFrame,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        tint: Color,
+    ) -> bool {
+        let tint = self.fade(tint);
+        self.target.draw_sprite(sprite, x, y, width, height, tint)
+    }
+}
+
+// Dessine à travers un autre Renderer en éclairant toutes les couleurs par
+// une même lumière (voir Color::tint)
+pub struct TintedRenderer<'a> {
+    target: &'a mut dyn Renderer,
+    light: Color,
+}
+
+impl<'a> TintedRenderer<'a> {
+    pub fn new(target: &'a mut dyn Renderer, light: Color) -> Self {
+        TintedRenderer { target, light }
+    }
+}
+
+impl Renderer for TintedRenderer<'_> {
+    fn clear_background(&mut self, color: Color) {
+        self.target.clear_background(color.tint(self.light));
+    }
+
+    fn draw_rectangle(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        let color = color.tint(self.light);
+        self.target.draw_rectangle(x, y, width, height, color);
+    }
+
+    fn draw_rectangle_lines(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        let color = color.tint(self.light);
+        self.target.draw_rectangle_lines(x, y, width, height, color);
+    }
+
+    fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        let color = color.tint(self.light);
+        self.target.draw_triangle(v1, v2, v3, color);
+    }
+
+    fn draw_circle(&mut self, center_x: i32, center_y: i32, radius: f32, color: Color) {
+        let color = color.tint(self.light);
+        self.target.draw_circle(center_x, center_y, radius, color);
+    }
+
+    fn draw_line(&mut self, start_x: i32, start_y: i32, end_x: i32, end_y: i32, color: Color) {
+        let color = color.tint(self.light);
+        self.target.draw_line(start_x, start_y, end_x, end_y, color);
+    }
+
+    fn draw_text(&mut self, text: &str, x: i32, y: i32, font_size: i32, color: Color) {
+        let color = color.tint(self.light);
+        self.target.draw_text(text, x, y, font_size, color);
+    }
+
+    fn measure_text(&self, text: &str, font_size: i32) -> i32 {
+        self.target.measure_text(text, font_size)
+    }
+
+    fn draw_sprite(
+        &mut self,
+        sprite: SpriteFrame,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        tint: Color,
+    ) -> bool {
+        let tint = tint.tint(self.light);
+        self.target.draw_sprite(sprite, x, y, width, height, tint)
+    }
+}
+
+// Appel de dessin capturé par RecordingRenderer
+#[derive(Clone, Debug, PartialEq)]
+pub enum DrawCommand {
+    Clear {
+        color: Color,
+    },
+    Rectangle {
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        color: Color,
+    },
+    RectangleLines {
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        color: Color,
+    },
+    Triangle {
+        v1: Vector2,
+        v2: Vector2,
+        v3: Vector2,
+        color: Color,
+    },
+    Circle {
+        center_x: i32,
+        center_y: i32,
+        radius: f32,
+        color: Color,
+    },
+    Line {
+        start_x: i32,
+        start_y: i32,
+        end_x: i32,
+        end_y: i32,
+        color: Color,
+    },
+    Text {
+        text: String,
+        x: i32,
+        y: i32,
+        font_size: i32,
+        color: Color,
+    },
+    Sprite {
+        sheet: &'static str,
+        frame: usize, // Image demandée, avant l'animation de la feuille
+        flip_x: bool,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        tint: Color,
+    },
+}
+
+// Renderer qui n'affiche rien mais conserve la liste des appels de dessin,
+// pour vérifier ce qu'émet un Drawable sans carte graphique
+#[derive(Default)]
+pub struct RecordingRenderer {
+    commands: Vec<DrawCommand>,
+}
+
+impl RecordingRenderer {
+    pub fn new() -> Self {
+        RecordingRenderer {
+            commands: Vec::new(),
+        }
+    }
+
+    pub fn get_commands(&self) -> &[DrawCommand] {
+        &self.commands
+    }
+
+    // Récupérer les appels capturés et vider la liste
+    pub fn take_commands(&mut self) -> Vec<DrawCommand> {
+        std::mem::take(&mut self.commands)
+    }
+}
+
+impl Renderer for RecordingRenderer {
+    fn clear_background(&mut self, color: Color) {
+        self.commands.push(DrawCommand::Clear { color });
+    }
+
+    fn draw_rectangle(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        self.commands.push(DrawCommand::Rectangle {
+            x,
+            y,
+            width,
+            height,
+            color,
+        });
+    }
+
+    fn draw_rectangle_lines(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        self.commands.push(DrawCommand::RectangleLines {
+            x,
+            y,
+            width,
+            height,
+            color,
+        });
+    }
+
+    fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        self.commands
+            .push(DrawCommand::Triangle { v1, v2, v3, color });
+    }
+
+    fn draw_circle(&mut self, center_x: i32, center_y: i32, radius: f32, color: Color) {
+        self.commands.push(DrawCommand::Circle {
+            center_x,
+            center_y,
+            radius,
+            color,
+        });
+    }
+
+    fn draw_line(&mut self, start_x: i32, start_y: i32, end_x: i32, end_y: i32, color: Color) {
+        self.commands.push(DrawCommand::Line {
+            start_x,
+            start_y,
+            end_x,
+            end_y,
+            color,
+        });
+    }
+
+    fn draw_text(&mut self, text: &str, x: i32, y: i32, font_size: i32, color: Color) {
+        self.commands.push(DrawCommand::Text {
+            text: text.to_string(),
+            x,
+            y,
+            font_size,
+            color,
+        });
+    }
+
+    // Pas de police : largeur moyenne d'un caractère de la police par défaut
+    fn measure_text(&self, text: &str, font_size: i32) -> i32 {
+        text.chars().count() as i32 * font_size / 2
+    }
+
+    // Capturé puis refusé, comme par un Renderer sans feuilles : le dessin
+    // procédural de l'appelant est capturé à la suite
+    fn draw_sprite(
+        &mut self,
+        sprite: SpriteFrame,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        tint: Color,
+    ) -> bool {
+        self.commands.push(DrawCommand::Sprite {
+            sheet: sprite.sheet,
+            frame: sprite.frame,
+            flip_x: sprite.flip_x,
+            x,
+            y,
+            width,
+            height,
+            tint,
+        });
+        false
+    }
+}
+
+// Implémentation raylib : le handle de dessin d'une frame est un Renderer
+#[cfg(feature = "raylib")]
+impl Renderer for raylib::prelude::RaylibDrawHandle<'_> {
+    fn clear_background(&mut self, color: Color) {
+        raylib::prelude::RaylibDraw::clear_background(self, color);
+    }
+
+    fn draw_rectangle(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        raylib::prelude::RaylibDraw::draw_rectangle(self, x, y, width, height, color);
+    }
+
+    fn draw_rectangle_lines(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        raylib::prelude::RaylibDraw::draw_rectangle_lines(self, x, y, width, height, color);
+    }
+
+    fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        raylib::prelude::RaylibDraw::draw_triangle(self, v1, v2, v3, color);
+    }
+
+    fn draw_circle(&mut self, center_x: i32, center_y: i32, radius: f32, color: Color) {
+        raylib::prelude::RaylibDraw::draw_circle(self, center_x, center_y, radius, color);
+    }
+
+    fn draw_line(&mut self, start_x: i32, start_y: i32, end_x: i32, end_y: i32, color: Color) {
+        raylib::prelude::RaylibDraw::draw_line(self, start_x, start_y, end_x, end_y, color);
+    }
+
+    fn draw_text(&mut self, text: &str, x: i32, y: i32, font_size: i32, color: Color) {
+        raylib::prelude::RaylibDraw::draw_text(self, text, x, y, font_size, color);
+    }
+
+    fn measure_text(&self, text: &str, font_size: i32) -> i32 {
+        raylib::prelude::RaylibHandle::measure_text(self, text, font_size)
+    }
+}
+
+// Renderer raylib qui dessine aussi les feuilles de sprites chargées sur la
+// carte graphique
+#[cfg(feature = "raylib")]
+pub struct TexturedRenderer<'a, 'b> {
+    target: &'a mut raylib::prelude::RaylibDrawHandle<'b>,
+    textures: &'a crate::sprite::SpriteTextures,
+}
+
+#[cfg(feature = "raylib")]
+impl<'a, 'b> TexturedRenderer<'a, 'b> {
+    pub fn new(
+        target: &'a mut raylib::prelude::RaylibDrawHandle<'b>,
+        textures: &'a crate::sprite::SpriteTextures,
+    ) -> Self {
+        TexturedRenderer { target, textures }
+    }
+}
+
+#[cfg(feature = "raylib")]
+impl Renderer for TexturedRenderer<'_, '_> {
+    fn clear_background(&mut self, color: Color) {
+        Renderer::clear_background(self.target, color);
+    }
+
+    fn draw_rectangle(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        Renderer::draw_rectangle(self.target, x, y, width, height, color);
+    }
+
+    fn draw_rectangle_lines(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        Renderer::draw_rectangle_lines(self.target, x, y, width, height, color);
+    }
+
+    fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        Renderer::draw_triangle(self.target, v1, v2, v3, color);
+    }
+
+    fn draw_circle(&mut self, center_x: i32, center_y: i32, radius: f32, color: Color) {
+        Renderer::draw_circle(self.target, center_x, center_y, radius, color);
+    }
+
+    fn draw_line(&mut self, start_x: i32, start_y: i32, end_x: i32, end_y: i32, color: Color) {
+        Renderer::draw_line(self.target, start_x, start_y, end_x, end_y, color);
+    }
+
+    fn draw_text(&mut self, text: &str, x: i32, y: i32, font_size: i32, color: Color) {
+        Renderer::draw_text(self.target, text, x, y, font_size, color);
+    }
+
+    fn measure_text(&self, text: &str, font_size: i32) -> i32 {
+        Renderer::measure_text(&*self.target, text, font_size)
+    }
+
+    fn draw_sprite(
+        &mut self,
+        sprite: SpriteFrame,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        tint: Color,
+    ) -> bool {
+        let Some((sheet, texture)) = self.textures.get(sprite.sheet) else {
+            return false;
+        };
+
+        // Une largeur de source négative retourne l'image horizontalement
+        let (frame, flip_x) = sheet.resolve_frame(&sprite);
+        let (source_x, source_y, source_width, source_height) = sheet.get_frame_rect(frame);
+        let source_width = if flip_x {
+            -(source_width as f32)
+        } else {
+            source_width as f32
+        };
+        let source = raylib::prelude::Rectangle::new(
+            source_x as f32,
+            source_y as f32,
+            source_width,
+            source_height as f32,
+        );
+        let dest = raylib::prelude::Rectangle::new(x as f32, y as f32, width as f32, height as f32);
+        raylib::prelude::RaylibDraw::draw_texture_pro(
+            self.target,
+            texture,
+            source,
+            dest,
+            Vector2::default(),
+            0.0,
+            tint,
+        );
+        true
+    }
+}
@@ -0,0 +1,374 @@
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::iter::Peekable;
+use std::path::Path;
+
+use crate::config::GameConfig;
+use crate::game::{GameState, Input};
+
+const REPLAY_HEADER: &str = "vibegame-replay 2";
+
+// Format précédent, sans configuration, toujours relu
+const REPLAY_HEADER_V1: &str = "vibegame-replay 1";
+
+// Entrées d'une frame et durée de cette frame
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReplayFrame {
+    pub frame_time: f32,
+    pub input: Input,
+}
+
+// Enregistrement d'une partie : la graine, la configuration du début de la
+// partie, les rechargements de configuration et les entrées de chaque frame
+// suffisent à la rejouer à l'identique
+//
+// Format texte :
+//   vibegame-replay 2
+//   seed <graine>
+//   config <ligne TOML>                          (une ligne par ligne de la configuration)
+//   <durée de frame> <direction> <espace 0|1>   (une ligne par frame)
+//   reload <index de frame>                      (rechargement appliqué avant cette frame,
+//   config <ligne TOML>                           suivi de la nouvelle configuration)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replay {
+    seed: u64,
+    config: Option<GameConfig>, // Absente des replays au format 1
+    frames: Vec<ReplayFrame>,
+    reloads: Vec<(usize, GameConfig)>, // Index de la frame suivante, configuration rechargée
+}
+
+impl Replay {
+    pub fn new(seed: u64, config: GameConfig) -> Self {
+        Replay {
+            seed,
+            config: Some(config),
+            frames: Vec::new(),
+            reloads: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, input: Input, frame_time: f32) {
+        self.frames.push(ReplayFrame { frame_time, input });
+    }
+
+    // Noter une configuration rechargée (GameState::apply_tuning) avant la
+    // prochaine frame enregistrée
+    pub fn record_reload(&mut self, config: GameConfig) {
+        self.reloads.push((self.frames.len(), config));
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn get_config(&self) -> Option<&GameConfig> {
+        self.config.as_ref()
+    }
+
+    pub fn get_frames(&self) -> &[ReplayFrame] {
+        &self.frames
+    }
+
+    // Configurations rechargées juste avant la frame `frame`
+    pub fn get_reloads(&self, frame: usize) -> impl Iterator<Item = &GameConfig> {
+        self.reloads
+            .iter()
+            .filter(move |(index, _)| *index == frame)
+            .map(|(_, config)| config)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_text())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Replay> {
+        Replay::from_text(&fs::read_to_string(path)?)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}\nseed {}\n", REPLAY_HEADER, self.seed);
+        if let Some(config) = &self.config {
+            push_config(&mut text, config);
+        }
+        for index in 0..=self.frames.len() {
+            for config in self.get_reloads(index) {
+                text.push_str(&format!("reload {}\n", index));
+                push_config(&mut text, config);
+            }
+            if let Some(frame) = self.frames.get(index) {
+                // L'affichage des flottants est exact à la relecture
+                text.push_str(&format!(
+                    "{} {} {}\n",
+                    frame.frame_time, frame.input.direction, frame.input.space_pressed as u8
+                ));
+            }
+        }
+        text
+    }
+
+    pub fn from_text(text: &str) -> io::Result<Replay> {
+        let mut lines = text.lines().enumerate().peekable();
+
+        let has_config = match lines.next().map(|(_, line)| line.trim()) {
+            Some(REPLAY_HEADER) => true,
+            Some(REPLAY_HEADER_V1) => false,
+            _ => {
+                return Err(invalid_data(format!(
+                    "en-tête attendu : \"{}\"",
+                    REPLAY_HEADER
+                )))
+            }
+        };
+
+        let seed = match lines.next() {
+            Some((_, line)) => line
+                .trim()
+                .strip_prefix("seed ")
+                .and_then(|seed| seed.trim().parse::<u64>().ok())
+                .ok_or_else(|| invalid_data("ligne 2 : \"seed <graine>\" attendu".to_string()))?,
+            None => return Err(invalid_data("graine manquante".to_string())),
+        };
+
+        let mut replay = Replay {
+            seed,
+            config: None,
+            frames: Vec::new(),
+            reloads: Vec::new(),
+        };
+        if has_config {
+            replay.config = Some(read_config(&mut lines)?);
+        }
+
+        while let Some((index, line)) = lines.next() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(frame) = line.trim().strip_prefix("reload ") {
+                // Le rechargement doit tomber à sa place dans la suite des frames
+                if !has_config || frame.trim().parse::<usize>().ok() != Some(replay.frames.len()) {
+                    return Err(invalid_data(format!(
+                        "ligne {} : rechargement inattendu \"{}\"",
+                        index + 1,
+                        line
+                    )));
+                }
+                let config = read_config(&mut lines)?;
+                replay.reloads.push((replay.frames.len(), config));
+                continue;
+            }
+            let frame = parse_frame(line).ok_or_else(|| {
+                invalid_data(format!("ligne {} : frame invalide \"{}\"", index + 1, line))
+            })?;
+            replay.frames.push(frame);
+        }
+
+        Ok(replay)
+    }
+}
+
+// Écrire une configuration, une ligne TOML par ligne préfixée par `config`
+fn push_config(text: &mut String, config: &GameConfig) {
+    for line in config.to_toml().lines().filter(|line| !line.is_empty()) {
+        text.push_str(&format!("config {}\n", line));
+    }
+}
+
+// Lire les lignes `config` qui suivent
+fn read_config<'a>(
+    lines: &mut Peekable<impl Iterator<Item = (usize, &'a str)>>,
+) -> io::Result<GameConfig> {
+    let mut toml = String::new();
+    while let Some(line) = lines
+        .peek()
+        .and_then(|(_, line)| line.trim().strip_prefix("config "))
+    {
+        toml.push_str(&format!("{}\n", line));
+        lines.next();
+    }
+    GameConfig::parse(&toml, "config").map_err(|error| invalid_data(error.to_string()))
+}
+
+fn parse_frame(line: &str) -> Option<ReplayFrame> {
+    let mut fields = line.split_whitespace();
+    let frame_time = fields.next()?.parse::<f32>().ok()?;
+    let direction = fields.next()?.parse::<f32>().ok()?;
+    let space_pressed = match fields.next()? {
+        "0" => false,
+        "1" => true,
+        _ => return None,
+    };
+    if fields.next().is_some() {
+        return None;
+    }
+
+    Some(ReplayFrame {
+        frame_time,
+        input: Input::new(direction, space_pressed),
+    })
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(
+        ErrorKind::InvalidData,
+        format!("replay invalide : {}", message),
+    )
+}
+
+// Relecture d'un enregistrement, frame par frame, à la place du clavier
+pub struct ReplayPlayer {
+    replay: Replay,
+    next_frame: usize,
+    next_reload: usize, // Premier rechargement pas encore rendu par take_reloads
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Self {
+        ReplayPlayer {
+            replay,
+            next_frame: 0,
+            next_reload: 0,
+        }
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.replay.seed
+    }
+
+    pub fn get_config(&self) -> Option<&GameConfig> {
+        self.replay.get_config()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_frame >= self.replay.frames.len()
+    }
+
+    // Configurations à appliquer (GameState::apply_tuning) avant la
+    // prochaine frame ; chacune n'est rendue qu'une fois
+    pub fn take_reloads(&mut self) -> Vec<GameConfig> {
+        let reloads: Vec<GameConfig> = self.replay.reloads[self.next_reload..]
+            .iter()
+            .take_while(|(frame, _)| *frame <= self.next_frame)
+            .map(|(_, config)| config.clone())
+            .collect();
+        self.next_reload += reloads.len();
+        reloads
+    }
+
+    pub fn next_frame(&mut self) -> Option<ReplayFrame> {
+        let frame = self.replay.frames.get(self.next_frame).copied();
+        if frame.is_some() {
+            self.next_frame += 1;
+        }
+        frame
+    }
+}
+
+// Rejouer un enregistrement sans fenêtre et renvoyer l'état final de la partie,
+// avec la configuration enregistrée et ses rechargements (`config` sert pour
+// les replays au format 1, qui n'en ont pas) ; `frame_limit` arrête la
+// relecture après ce nombre de frames
+pub fn run_headless(replay: &Replay, config: GameConfig, frame_limit: Option<usize>) -> GameState {
+    let config = replay.config.clone().unwrap_or(config);
+    let mut game = GameState::new(config, replay.seed);
+    let frame_count = frame_limit.unwrap_or(replay.frames.len());
+    for (index, frame) in replay.frames.iter().enumerate().take(frame_count) {
+        for config in replay.get_reloads(index) {
+            game.apply_tuning(config);
+        }
+        game.update(frame.input, frame.frame_time);
+        // Personne n'écoute les événements sans fenêtre
+        game.get_events_mut().take_events();
+    }
+    game
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Une partie jouée en direct, enregistrée, écrite puis relue se rejoue
+    // à l'identique, avec sa configuration et non celle passée en secours
+    #[test]
+    fn recorded_run_replays_identically() {
+        let mut config = GameConfig::default();
+        config.physics.gravity = 1500.0;
+        config.physics.player_speed = 260.0;
+
+        let mut game = GameState::new(config.clone(), 7);
+        let mut replay = Replay::new(7, config);
+        for frame in 0..900 {
+            let input = Input::new(1.0, frame % 75 < 25);
+            let frame_time = if frame % 3 == 0 {
+                1.0 / 30.0
+            } else {
+                1.0 / 144.0
+            };
+            replay.record(input, frame_time);
+            game.update(input, frame_time);
+            game.get_events_mut().take_events();
+        }
+
+        let loaded = Replay::from_text(&replay.to_text()).unwrap();
+        assert_eq!(loaded, replay);
+
+        let replayed = run_headless(&loaded, GameConfig::default(), None);
+        assert!(game.get_score() > 0);
+        assert_eq!(replayed.get_score(), game.get_score());
+        assert_eq!(replayed.is_game_over(), game.is_game_over());
+        assert_eq!(replayed.get_player_world_x(), game.get_player_world_x());
+        assert_eq!(replayed.get_config(), game.get_config());
+    }
+
+    #[test]
+    fn version_1_replays_have_no_config() {
+        let replay = Replay::from_text("vibegame-replay 1\nseed 3\n0.016 1 0\n").unwrap();
+        assert_eq!(replay.get_seed(), 3);
+        assert!(replay.get_config().is_none());
+        assert_eq!(replay.get_frames().len(), 1);
+    }
+
+    // Les rechargements de configuration sont enregistrés à leur frame et
+    // réappliqués à la relecture
+    #[test]
+    fn reloads_are_replayed() {
+        let config = GameConfig::default();
+        let mut tuned = config.clone();
+        tuned.physics.jump_height = 120.0;
+        tuned.physics.player_speed = 320.0;
+
+        let mut game = GameState::new(config.clone(), 11);
+        let mut replay = Replay::new(11, config.clone());
+        for frame in 0..600 {
+            if frame == 200 {
+                game.apply_tuning(&tuned);
+                replay.record_reload(tuned.clone());
+            }
+            let input = Input::new(1.0, frame % 80 < 30);
+            replay.record(input, 1.0 / 60.0);
+            game.update(input, 1.0 / 60.0);
+            game.get_events_mut().take_events();
+        }
+
+        let text = replay.to_text();
+        assert!(text.contains("reload 200\n"));
+        let loaded = Replay::from_text(&text).unwrap();
+        assert_eq!(loaded, replay);
+
+        // Dans la fenêtre, chaque rechargement est rendu une fois, avant sa frame
+        let mut player = ReplayPlayer::new(loaded.clone());
+        let mut reloaded_at = Vec::new();
+        for frame in 0..=600 {
+            for config in player.take_reloads() {
+                assert_eq!(config, tuned);
+                reloaded_at.push(frame);
+            }
+            player.next_frame();
+        }
+        assert_eq!(reloaded_at, vec![200]);
+
+        let replayed = run_headless(&loaded, config, None);
+        assert_eq!(replayed.get_config(), game.get_config());
+        assert_eq!(replayed.get_score(), game.get_score());
+        assert_eq!(replayed.get_player_world_x(), game.get_player_world_x());
+    }
+}
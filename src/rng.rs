@@ -0,0 +1,24 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+// Générateur aléatoire du jeu : ChaCha8 garantit la même séquence pour une
+// graine donnée, quelle que soit la plateforme ou la version de rand
+pub type GameRng = ChaCha8Rng;
+
+pub fn create_rng(seed: u64) -> GameRng {
+    GameRng::seed_from_u64(seed)
+}
+
+// Tirer une graine au hasard (parties non reproductibles)
+pub fn random_seed() -> u64 {
+    rand::random()
+}
+
+// Générateur propre à un tronçon du monde : le contenu d'un tronçon ne dépend
+// que de la graine, du flux (couche de décor, obstacles...) et de son index,
+// quel que soit l'ordre dans lequel les tronçons sont créés
+pub fn create_chunk_rng(seed: u64, stream: u64, chunk: i64) -> GameRng {
+    let mut rng = GameRng::seed_from_u64(seed ^ (chunk as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    rng.set_stream(stream);
+    rng
+}
@@ -1,59 +1,179 @@
-use raylib::prelude::*;
+use std::collections::BTreeMap;
 
-use crate::drawable::Drawable;
+use crate::biome::Palette;
+use crate::camera::Camera;
+use crate::color::Color;
+use crate::drawable::{
+    Drawable, LAYER_BACKGROUND, LAYER_FOREGROUND, LAYER_GAMEPLAY, LAYER_MIDDLE,
+};
+use crate::math::Vector2;
+use crate::render_queue::RenderQueue;
+use crate::renderer::Renderer;
 
-// Collection d'éléments de décor
+// Largeur d'un tronçon de décor (pixels du monde)
+pub const SCENERY_CHUNK_WIDTH: f32 = 512.0;
+
+// Tronçons gardés en mémoire de part et d'autre de la vue : ceux de devant
+// sont générés avant d'apparaître
+const STREAM_MARGIN_CHUNKS: i64 = 1;
+
+// Couches du décor, générées séparément
+const SCENERY_LAYERS: [i32; 4] = [
+    LAYER_BACKGROUND,
+    LAYER_MIDDLE,
+    LAYER_GAMEPLAY,
+    LAYER_FOREGROUND,
+];
+
+// Index du tronçon qui contient l'abscisse `x`
+pub fn chunk_index(x: f32) -> i64 {
+    (x / SCENERY_CHUNK_WIDTH).floor() as i64
+}
+
+// Éléments d'une couche, rangés par tronçon selon leur bord gauche
+#[derive(Default)]
+struct SceneryLayer {
+    chunks: BTreeMap<i64, Vec<Box<dyn Drawable>>>,
+    max_width: f32, // Largeur du plus grand élément, qui peut déborder sur les tronçons suivants
+}
+
+// Collection d'éléments de décor, indexée par couche et par tronçon
+// horizontal pour ne parcourir que ce qui est proche de la vue
 pub struct Scenery {
-    elements: Vec<Box<dyn Drawable>>,
+    layers: BTreeMap<i32, SceneryLayer>,
+    element_count: usize,
+}
+
+impl Default for Scenery {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Scenery {
     pub fn new() -> Self {
         Scenery {
-            elements: Vec::new(),
+            layers: BTreeMap::new(),
+            element_count: 0,
         }
     }
 
     // Méthode pour ajouter un élément déjà construit
-    // fn push(&mut self, element: Box<dyn Drawable>) {
-    //     self.elements.push(element);
-    // }
+    pub fn push(&mut self, element: Box<dyn Drawable>) {
+        let (x, _) = element.get_position();
+        let (width, _) = element.get_size();
+
+        let layer = self.layers.entry(element.get_layer()).or_default();
+        layer.max_width = layer.max_width.max(width);
+        layer.chunks.entry(chunk_index(x)).or_default().push(element);
+        self.element_count += 1;
+    }
 
     // Méthode pour ajouter plusieurs éléments
     pub fn extend(&mut self, elements: Vec<Box<dyn Drawable>>) {
         for element in elements {
-            self.elements.push(element);
-        }
-    }
-
-    // Méthode pour dessiner tous les éléments par couche
-    pub fn draw(&self, d: &mut RaylibDrawHandle, camera_offset: f32, screen_width: f32) {
-        // Dessiner par couche (0 = arrière-plan, 1 = milieu, 2 = premier plan)
-        for layer in 0..3 {
-            for element in &self.elements {
-                if element.get_layer() == layer {
-                    let (x, _) = element.get_position();
-                    let (width, _) = element.get_size();
-
-                    // Différentes vitesses de parallaxe selon la couche
-                    let parallax_factor = match layer {
-                        0 => 0.5, // Arrière-plan
-                        1 => 0.8, // Milieu
-                        _ => 1.0, // Premier plan
-                    };
-
-                    let screen_x = x - camera_offset * parallax_factor;
-                    
-                    // Vérification plus précise avec la taille
-                    if screen_x + width >= 0.0 && screen_x <= screen_width {
-                        element.draw(d, screen_x, screen_width);
-                    }
+            self.push(element);
+        }
+    }
+
+    // Ranger les éléments générés pour le tronçon `chunk` d'une couche
+    // (les éléments doivent commencer dans ce tronçon) ; un tronçon vide est
+    // aussi retenu pour ne pas être regénéré
+    pub fn insert_chunk(&mut self, layer: i32, chunk: i64, elements: Vec<Box<dyn Drawable>>) {
+        let layer = self.layers.entry(layer).or_default();
+        for element in &elements {
+            layer.max_width = layer.max_width.max(element.get_size().0);
+        }
+        self.element_count += elements.len();
+        layer.chunks.entry(chunk).or_default().extend(elements);
+    }
+
+    pub fn has_chunk(&self, layer: i32, chunk: i64) -> bool {
+        self.layers
+            .get(&layer)
+            .is_some_and(|layer| layer.chunks.contains_key(&chunk))
+    }
+
+    // Créer les tronçons qui entrent dans la vue (décalée selon la parallaxe
+    // de chaque couche) et supprimer ceux qui en sont sortis ; `generate`
+    // fournit le contenu d'un tronçon à partir de sa couche et de son index
+    pub fn stream(
+        &mut self,
+        camera: &Camera,
+        mut generate: impl FnMut(i32, i64) -> Vec<Box<dyn Drawable>>,
+    ) {
+        for layer_index in SCENERY_LAYERS {
+            let parallax_factor = parallax_factor(layer_index);
+            let left = camera
+                .screen_to_world_parallax(Vector2::new(0.0, 0.0), parallax_factor)
+                .x;
+            let right = left + camera.get_view_width();
+            let first = chunk_index(left) - STREAM_MARGIN_CHUNKS;
+            let last = chunk_index(right) + STREAM_MARGIN_CHUNKS;
+
+            // Oublier les tronçons trop loin de la vue
+            let layer = self.layers.entry(layer_index).or_default();
+            let mut removed = 0;
+            layer.chunks.retain(|chunk, elements| {
+                let keep = (first..=last).contains(chunk);
+                if !keep {
+                    removed += elements.len();
+                }
+                keep
+            });
+            self.element_count -= removed;
+
+            for chunk in first..=last {
+                if !self.has_chunk(layer_index, chunk) {
+                    let elements = generate(layer_index, chunk);
+                    self.insert_chunk(layer_index, chunk, elements);
+                }
+            }
+        }
+    }
+
+    pub fn get_element_count(&self) -> usize {
+        self.element_count
+    }
+
+    // Ajouter à la file de rendu les éléments visibles ; seuls les tronçons
+    // qui croisent la vue (décalée selon la parallaxe de la couche) sont
+    // parcourus, le tri par couche est fait par la file
+    pub fn queue_draw<'a>(&'a self, queue: &mut RenderQueue<'a>, camera: &Camera) {
+        for (&layer_index, layer) in &self.layers {
+            let parallax_factor = parallax_factor(layer_index);
+            let left = camera
+                .screen_to_world_parallax(Vector2::new(0.0, 0.0), parallax_factor)
+                .x;
+            let right = left + camera.get_view_width();
+
+            // Un élément rangé plus à gauche peut encore dépasser dans la vue
+            let chunks = chunk_index(left - layer.max_width)..=chunk_index(right);
+            for element in layer.chunks.range(chunks).flat_map(|(_, elements)| elements) {
+                let (x, y) = element.get_position();
+                let (width, _) = element.get_size();
+
+                let screen = camera.world_to_screen_parallax(Vector2::new(x, y), parallax_factor);
+
+                // Vérification plus précise avec la taille
+                if camera.is_visible(screen.x, width) {
+                    queue.push(element.as_ref(), screen);
                 }
             }
         }
     }
 }
 
+// Différentes vitesses de parallaxe selon la couche
+pub fn parallax_factor(layer: i32) -> f32 {
+    match layer {
+        LAYER_BACKGROUND => 0.5,
+        LAYER_MIDDLE => 0.8,
+        LAYER_FOREGROUND => 1.2, // Défile plus vite que le joueur
+        _ => 1.0,
+    }
+}
+
 // Structure de base pour un élément de décor
 pub struct SceneryElement {
     x: f32,
@@ -75,14 +195,18 @@ impl SceneryElement {
             layer,
         }
     }
+
+    pub fn get_color(&self) -> Color {
+        self.color
+    }
 }
 
 impl Drawable for SceneryElement {
-    fn draw(&self, d: &mut RaylibDrawHandle, screen_x: f32, screen_width: f32) {
+    fn draw(&self, d: &mut dyn Renderer, screen_x: f32, screen_y: f32, screen_width: f32) {
         if screen_x + self.width >= 0.0 && screen_x <= screen_width {
             d.draw_rectangle(
                 screen_x as i32,
-                self.y as i32,
+                screen_y as i32,
                 self.width as i32,
                 self.height as i32,
                 self.color,
@@ -104,13 +228,17 @@ impl Drawable for SceneryElement {
 }
 
 // Énumération des types d'éléments de décor
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SceneryType {
     Tree,
     Cloud,
     Mountain,
     Ground,
     GrassTuft,
+    Bush, // Buisson d'avant-plan, devant le joueur
+    Cactus,
+    Building,   // Immeuble d'arrière-plan, avec des fenêtres allumées
+    StreetLamp,
 }
 
 // Fonction pour créer un élément spécifique
@@ -120,6 +248,7 @@ pub fn create_scenery_element(
     y: f32,
     params: Option<(f32, f32)>,
     ground_level: f32,
+    palette: &Palette,
 ) -> Vec<Box<dyn Drawable>> {
     let mut elements: Vec<Box<dyn Drawable>> = Vec::new();
 
@@ -133,8 +262,8 @@ pub fn create_scenery_element(
                 ground_level - height,
                 20.0,
                 height,
-                Color::new(139, 69, 19, 255), // Marron
-                2,                            // Premier plan
+                palette.trunk,
+                LAYER_GAMEPLAY,
             )) as Box<dyn Drawable>);
 
             // Feuillage
@@ -143,8 +272,8 @@ pub fn create_scenery_element(
                 ground_level - height - 60.0,
                 80.0,
                 80.0,
-                Color::new(34, 139, 34, 255), // Vert forêt
-                2,                            // Premier plan
+                palette.foliage,
+                LAYER_GAMEPLAY,
             )) as Box<dyn Drawable>);
         }
         SceneryType::Cloud => {
@@ -155,8 +284,8 @@ pub fn create_scenery_element(
                 y,
                 width,
                 height,
-                Color::WHITE,
-                1, // Milieu
+                palette.cloud,
+                LAYER_MIDDLE,
             )) as Box<dyn Drawable>);
         }
         SceneryType::Mountain => {
@@ -167,18 +296,20 @@ pub fn create_scenery_element(
                 ground_level - height,
                 300.0,
                 height,
-                Color::new(128, 128, 128, 255), // Gris
-                0,                              // Arrière-plan
+                palette.background,
+                LAYER_BACKGROUND,
             )) as Box<dyn Drawable>);
         }
         SceneryType::Ground => {
+            let (width, height) = params.unwrap_or((200.0, 50.0));
+
             elements.push(Box::new(SceneryElement::new(
                 x,
                 ground_level,
-                200.0,
-                50.0,
-                Color::new(76, 153, 0, 255), // Vert herbe
-                2,                           // Premier plan
+                width,
+                height,
+                palette.surface,
+                LAYER_GAMEPLAY,
             )) as Box<dyn Drawable>);
         }
         SceneryType::GrassTuft => {
@@ -187,11 +318,183 @@ pub fn create_scenery_element(
                 ground_level - 10.0,
                 15.0,
                 10.0,
-                Color::new(102, 204, 0, 255), // Vert herbe plus vif
-                2,                            // Premier plan
+                palette.grass,
+                LAYER_GAMEPLAY,
+            )) as Box<dyn Drawable>);
+        }
+        SceneryType::Bush => {
+            let (width, height) = params.unwrap_or((120.0, 50.0));
+
+            // Posé en bas de l'écran, sous les pieds du joueur
+            elements.push(Box::new(SceneryElement::new(
+                x,
+                y - height,
+                width,
+                height,
+                palette.bush,
+                LAYER_FOREGROUND,
+            )) as Box<dyn Drawable>);
+        }
+        SceneryType::Cactus => {
+            let height = params.unwrap_or((80.0, 0.0)).0;
+
+            // Tige
+            elements.push(Box::new(SceneryElement::new(
+                x,
+                ground_level - height,
+                20.0,
+                height,
+                palette.foliage,
+                LAYER_GAMEPLAY,
+            )) as Box<dyn Drawable>);
+
+            // Bras gauche, plus bas que le droit
+            elements.push(Box::new(SceneryElement::new(
+                x - 20.0,
+                ground_level - height * 0.6,
+                20.0,
+                10.0,
+                palette.foliage,
+                LAYER_GAMEPLAY,
+            )) as Box<dyn Drawable>);
+            elements.push(Box::new(SceneryElement::new(
+                x - 20.0,
+                ground_level - height * 0.6 - 20.0,
+                10.0,
+                20.0,
+                palette.foliage,
+                LAYER_GAMEPLAY,
+            )) as Box<dyn Drawable>);
+
+            // Bras droit
+            elements.push(Box::new(SceneryElement::new(
+                x + 20.0,
+                ground_level - height * 0.8,
+                20.0,
+                10.0,
+                palette.foliage,
+                LAYER_GAMEPLAY,
+            )) as Box<dyn Drawable>);
+            elements.push(Box::new(SceneryElement::new(
+                x + 30.0,
+                ground_level - height * 0.8 - 20.0,
+                10.0,
+                20.0,
+                palette.foliage,
+                LAYER_GAMEPLAY,
+            )) as Box<dyn Drawable>);
+        }
+        SceneryType::Building => {
+            let (width, height) = params.unwrap_or((120.0, 200.0));
+
+            elements.push(Box::new(SceneryElement::new(
+                x,
+                ground_level - height,
+                width,
+                height,
+                palette.background,
+                LAYER_BACKGROUND,
+            )) as Box<dyn Drawable>);
+
+            // Fenêtres, allumées selon un motif fixe (l'immeuble est
+            // identique à chaque fois qu'il est recréé)
+            let columns = ((width - 10.0) / 20.0) as i32;
+            let rows = ((height - 20.0) / 25.0) as i32;
+            for row in 0..rows {
+                for column in 0..columns {
+                    if (row * 7 + column * 3 + x as i32) % 4 != 0 {
+                        continue;
+                    }
+
+                    elements.push(Box::new(SceneryElement::new(
+                        x + 10.0 + column as f32 * 20.0,
+                        ground_level - height + 15.0 + row as f32 * 25.0,
+                        10.0,
+                        12.0,
+                        palette.foliage,
+                        LAYER_BACKGROUND,
+                    )) as Box<dyn Drawable>);
+                }
+            }
+        }
+        SceneryType::StreetLamp => {
+            let height = params.unwrap_or((140.0, 0.0)).0;
+
+            // Poteau
+            elements.push(Box::new(SceneryElement::new(
+                x,
+                ground_level - height,
+                8.0,
+                height,
+                palette.trunk,
+                LAYER_GAMEPLAY,
+            )) as Box<dyn Drawable>);
+
+            // Lampe, qui déborde de chaque côté du poteau
+            elements.push(Box::new(SceneryElement::new(
+                x - 10.0,
+                ground_level - height - 10.0,
+                28.0,
+                10.0,
+                palette.foliage,
+                LAYER_GAMEPLAY,
             )) as Box<dyn Drawable>);
         }
     }
 
     elements
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CameraConfig;
+    use crate::utils::generate_scenery_chunk;
+
+    // Position et taille des éléments de chaque tronçon, par couche
+    type Snapshot = BTreeMap<(i32, i64), Vec<(f32, f32, f32, f32)>>;
+
+    fn visit(scenery: &mut Scenery, x: f32, seen: &mut Snapshot, generated: &mut usize) {
+        let camera = Camera::new(CameraConfig::default(), 800.0, 600.0, Vector2::new(x, 0.0));
+        scenery.stream(&camera, |layer, chunk| {
+            *generated += 1;
+            generate_scenery_chunk(layer, chunk, 7, 490.0, 600.0)
+        });
+        for (&layer_index, layer) in &scenery.layers {
+            for (&chunk, elements) in &layer.chunks {
+                let elements = elements
+                    .iter()
+                    .map(|element| {
+                        let ((x, y), (width, height)) = (element.get_position(), element.get_size());
+                        (x, y, width, height)
+                    })
+                    .collect::<Vec<_>>();
+                let first = seen.entry((layer_index, chunk)).or_insert_with(|| elements.clone());
+                assert_eq!(first, &elements, "tronçon {} de la couche {} régénéré différemment", chunk, layer_index);
+            }
+        }
+    }
+
+    // S'éloigner puis revenir : les tronçons oubliés sont régénérés à
+    // l'identique
+    #[test]
+    fn evicted_chunks_regenerate_identically() {
+        let mut scenery = Scenery::new();
+        let mut seen = Snapshot::new();
+        let mut generated = 0;
+
+        for x in (0..6000).step_by(50).chain((-3000..6000).rev().step_by(50)) {
+            visit(&mut scenery, x as f32, &mut seen, &mut generated);
+        }
+        for x in (-3000..3000).step_by(50) {
+            visit(&mut scenery, x as f32, &mut seen, &mut generated);
+        }
+
+        // Des tronçons ont bien été oubliés puis régénérés, et la mémoire
+        // reste limitée aux abords de la vue
+        assert!(generated > seen.len());
+        assert!(seen.values().any(|elements| !elements.is_empty()));
+        let loaded: usize = scenery.layers.values().map(|layer| layer.chunks.len()).sum();
+        assert!(loaded < seen.len() / 2);
+    }
+}
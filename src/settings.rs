@@ -0,0 +1,17 @@
+// Préférences du joueur modifiables depuis l'écran des paramètres
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    pub show_fps: bool,               // Afficher le compteur de FPS
+    pub screen_shake: bool,           // Secouer l'écran lors des chocs
+    pub transparent_foreground: bool, // Avant-plan transparent devant le joueur
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            show_fps: true,
+            screen_shake: true,
+            transparent_foreground: true,
+        }
+    }
+}
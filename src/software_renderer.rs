@@ -0,0 +1,481 @@
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, ErrorKind};
+use std::path::Path;
+
+use crate::color::Color;
+use crate::math::Vector2;
+use crate::renderer::Renderer;
+use crate::sprite::{SpriteAtlas, SpriteFrame};
+
+// Renderer sans carte graphique : les primitives sont rastérisées dans un
+// tampon RGBA en mémoire, qui peut être enregistré en PNG (images de
+// référence pour les tests, miniatures de replays sur un serveur)
+pub struct SoftwareRenderer {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>, // 4 octets (RGBA) par pixel, ligne par ligne
+    sprites: SpriteAtlas,
+}
+
+impl SoftwareRenderer {
+    // Image transparente de `width` x `height` pixels ; erreur si sa taille
+    // en octets dépasse la mémoire adressable
+    pub fn new(width: u32, height: u32) -> io::Result<Self> {
+        let size = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|pixels| pixels.checked_mul(4))
+            .ok_or_else(|| {
+                io::Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("image trop grande ({}x{})", width, height),
+                )
+            })?;
+        Ok(SoftwareRenderer {
+            width,
+            height,
+            pixels: vec![0; size],
+            sprites: SpriteAtlas::new(),
+        })
+    }
+
+    // Feuilles de sprites utilisées par draw_sprite
+    pub fn set_sprites(&mut self, sprites: SpriteAtlas) {
+        self.sprites = sprites;
+    }
+
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> u32 {
+        self.height
+    }
+
+    // Octets RGBA de l'image
+    pub fn get_pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        Some(Color::new(
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ))
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let file = File::create(path)?;
+        let mut encoder = png::Encoder::new(BufWriter::new(file), self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    // Charger une image RGBA 8 bits (comme celles écrites par save_png),
+    // par exemple pour la comparer à une image de référence
+    pub fn load_png(path: impl AsRef<Path>) -> io::Result<SoftwareRenderer> {
+        let decoder = png::Decoder::new(BufReader::new(File::open(path)?));
+        let mut reader = decoder.read_info()?;
+        let buffer_size = reader
+            .output_buffer_size()
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidData, "image PNG trop grande"))?;
+        let mut pixels = vec![0; buffer_size];
+        let info = reader.next_frame(&mut pixels)?;
+
+        if info.color_type != png::ColorType::Rgba || info.bit_depth != png::BitDepth::Eight {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "seules les images PNG RGBA 8 bits sont prises en charge",
+            ));
+        }
+
+        pixels.truncate(info.buffer_size());
+        Ok(SoftwareRenderer {
+            width: info.width,
+            height: info.height,
+            pixels,
+            sprites: SpriteAtlas::new(),
+        })
+    }
+
+    // Mélanger une couleur (avec sa transparence) sur le pixel existant
+    fn blend_pixel(&mut self, x: i32, y: i32, color: Color) {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return;
+        }
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+
+        if color.a == 255 {
+            self.pixels[i..i + 4].copy_from_slice(&[color.r, color.g, color.b, color.a]);
+            return;
+        }
+
+        let alpha = color.a as u32;
+        let inverse = 255 - alpha;
+        let mix = |src: u8, dst: u8| ((src as u32 * alpha + dst as u32 * inverse) / 255) as u8;
+        self.pixels[i] = mix(color.r, self.pixels[i]);
+        self.pixels[i + 1] = mix(color.g, self.pixels[i + 1]);
+        self.pixels[i + 2] = mix(color.b, self.pixels[i + 2]);
+        self.pixels[i + 3] = (alpha + self.pixels[i + 3] as u32 * inverse / 255) as u8;
+    }
+
+    fn fill_span(&mut self, x_start: i32, x_end: i32, y: i32, color: Color) {
+        for x in x_start.max(0)..x_end.min(self.width as i32) {
+            self.blend_pixel(x, y, color);
+        }
+    }
+}
+
+impl Renderer for SoftwareRenderer {
+    fn clear_background(&mut self, color: Color) {
+        for pixel in self.pixels.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+        }
+    }
+
+    fn draw_rectangle(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        for row in y.max(0)..(y + height).min(self.height as i32) {
+            self.fill_span(x, x + width, row, color);
+        }
+    }
+
+    fn draw_rectangle_lines(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        if width <= 0 || height <= 0 {
+            return;
+        }
+        let right = x + width - 1;
+        let bottom = y + height - 1;
+
+        self.fill_span(x, x + width, y, color);
+        if bottom > y {
+            self.fill_span(x, x + width, bottom, color);
+        }
+        for row in (y + 1)..bottom {
+            self.blend_pixel(x, row, color);
+            if right > x {
+                self.blend_pixel(right, row, color);
+            }
+        }
+    }
+
+    fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        // Un pixel est rempli si son centre est du même côté des trois arêtes
+        // (quel que soit l'ordre des sommets)
+        let edge = |a: Vector2, b: Vector2, px: f32, py: f32| {
+            (b.x - a.x) * (py - a.y) - (b.y - a.y) * (px - a.x)
+        };
+        let area = edge(v1, v2, v3.x, v3.y);
+        if area == 0.0 {
+            return;
+        }
+
+        let min_x = v1.x.min(v2.x).min(v3.x).floor().max(0.0) as i32;
+        let max_x = v1.x.max(v2.x).max(v3.x).ceil().min(self.width as f32) as i32;
+        let min_y = v1.y.min(v2.y).min(v3.y).floor().max(0.0) as i32;
+        let max_y = v1.y.max(v2.y).max(v3.y).ceil().min(self.height as f32) as i32;
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+                let w1 = edge(v2, v3, px, py) * area.signum();
+                let w2 = edge(v3, v1, px, py) * area.signum();
+                let w3 = edge(v1, v2, px, py) * area.signum();
+                if w1 >= 0.0 && w2 >= 0.0 && w3 >= 0.0 {
+                    self.blend_pixel(x, y, color);
+                }
+            }
+        }
+    }
+
+    fn draw_circle(&mut self, center_x: i32, center_y: i32, radius: f32, color: Color) {
+        if radius <= 0.0 {
+            return;
+        }
+        let reach = radius.ceil() as i32;
+        for y in (center_y - reach)..(center_y + reach) {
+            let dy = y as f32 + 0.5 - center_y as f32;
+            let half_width = radius * radius - dy * dy;
+            if half_width < 0.0 {
+                continue;
+            }
+            // Pixels dont le centre est dans le disque
+            let half_width = half_width.sqrt();
+            let x_start = (center_x as f32 - half_width - 0.5).ceil() as i32;
+            let x_end = (center_x as f32 + half_width - 0.5).floor() as i32 + 1;
+            self.fill_span(x_start, x_end, y, color);
+        }
+    }
+
+    fn draw_line(&mut self, start_x: i32, start_y: i32, end_x: i32, end_y: i32, color: Color) {
+        // Algorithme de Bresenham (extrémités incluses)
+        let dx = (end_x - start_x).abs();
+        let dy = -(end_y - start_y).abs();
+        let step_x = if start_x < end_x { 1 } else { -1 };
+        let step_y = if start_y < end_y { 1 } else { -1 };
+        let mut error = dx + dy;
+        let (mut x, mut y) = (start_x, start_y);
+
+        loop {
+            self.blend_pixel(x, y, color);
+            if x == end_x && y == end_y {
+                break;
+            }
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    fn draw_text(&mut self, text: &str, x: i32, y: i32, font_size: i32, color: Color) {
+        // Police bitmap 3x5 agrandie selon la taille demandée
+        let scale = (font_size / GLYPH_CELL_HEIGHT).max(1);
+        let mut cursor_x = x;
+
+        for character in text.chars() {
+            let glyph = glyph_for(character);
+            for row in 0..5 {
+                for column in 0..3 {
+                    if glyph & (1 << (14 - (row * 3 + column))) != 0 {
+                        self.draw_rectangle(
+                            cursor_x + column * scale,
+                            y + (row + 1) * scale,
+                            scale,
+                            scale,
+                            color,
+                        );
+                    }
+                }
+            }
+            cursor_x += 4 * scale;
+        }
+    }
+
+    fn measure_text(&self, text: &str, font_size: i32) -> i32 {
+        let scale = (font_size / GLYPH_CELL_HEIGHT).max(1);
+        let count = text.chars().count() as i32;
+        // Pas d'espacement après le dernier caractère
+        (count * 4 - 1).max(0) * scale
+    }
+
+    // Échantillonnage au plus proche voisin, sans lissage (pixel art)
+    fn draw_sprite(
+        &mut self,
+        sprite: SpriteFrame,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        tint: Color,
+    ) -> bool {
+        // Les feuilles sont mises de côté le temps de modifier les pixels
+        let sprites = std::mem::take(&mut self.sprites);
+        let Some(sheet) = sprites.get_sheet(sprite.sheet) else {
+            self.sprites = sprites;
+            return false;
+        };
+
+        let (frame, flip_x) = sheet.resolve_frame(&sprite);
+        let (source_x, source_y, source_width, source_height) = sheet.get_frame_rect(frame);
+        for row in y.max(0)..(y + height).min(self.height as i32) {
+            let v = ((row - y) as u32 * source_height) / height as u32;
+            for column in x.max(0)..(x + width).min(self.width as i32) {
+                let mut u = ((column - x) as u32 * source_width) / width as u32;
+                if flip_x {
+                    u = source_width - 1 - u;
+                }
+                let color = sheet.get_pixel(source_x + u, source_y + v).tint(tint);
+                let alpha = (color.a as u32 * tint.a as u32 / 255) as u8;
+                if alpha > 0 {
+                    self.blend_pixel(column, row, Color::new(color.r, color.g, color.b, alpha));
+                }
+            }
+        }
+
+        self.sprites = sprites;
+        true
+    }
+}
+
+// Hauteur d'un caractère en cellules (5 lignes de glyphe et une marge en haut
+// et en bas)
+const GLYPH_CELL_HEIGHT: i32 = 7;
+
+// Glyphes 3x5 : 5 lignes de 3 bits, de haut en bas
+fn glyph_for(character: char) -> u16 {
+    let rows: [u8; 5] = match fold_accent(character).to_ascii_uppercase() {
+        ' ' => [0b000, 0b000, 0b000, 0b000, 0b000],
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ',' => [0b000, 0b000, 0b000, 0b010, 0b100],
+        '!' => [0b010, 0b010, 0b010, 0b000, 0b010],
+        '%' => [0b101, 0b001, 0b010, 0b100, 0b101],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        '(' => [0b001, 0b010, 0b010, 0b010, 0b001],
+        ')' => [0b100, 0b010, 0b010, 0b010, 0b100],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        '|' => [0b010, 0b010, 0b010, 0b010, 0b010],
+        '<' => [0b001, 0b010, 0b100, 0b010, 0b001],
+        '>' => [0b100, 0b010, 0b001, 0b010, 0b100],
+        '=' => [0b000, 0b111, 0b000, 0b111, 0b000],
+        '\'' => [0b010, 0b010, 0b000, 0b000, 0b000],
+        _ => [0b111, 0b001, 0b011, 0b000, 0b010], // '?'
+    };
+
+    rows.iter().fold(0, |glyph, row| (glyph << 3) | *row as u16)
+}
+
+// Lettres accentuées courantes en français ramenées à la lettre de base
+fn fold_accent(character: char) -> char {
+    match character {
+        'à' | 'â' | 'ä' | 'À' | 'Â' => 'A',
+        'ç' | 'Ç' => 'C',
+        'é' | 'è' | 'ê' | 'ë' | 'É' | 'È' | 'Ê' => 'E',
+        'î' | 'ï' | 'Î' => 'I',
+        'ô' | 'ö' | 'Ô' => 'O',
+        'ù' | 'û' | 'ü' | 'Ù' | 'Û' => 'U',
+        _ => character,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawable::{Drawable, LAYER_GAMEPLAY};
+    use crate::obstacle::{Obstacle, ObstacleType};
+    use crate::player::Player;
+    use crate::scenery::SceneryElement;
+
+    // Empreinte FNV-1a des pixels, pour comparer une image à sa référence
+    fn pixel_hash(renderer: &SoftwareRenderer) -> u64 {
+        renderer
+            .get_pixels()
+            .iter()
+            .fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+                (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+            })
+    }
+
+    // Empreinte de l'image de procedural_art_matches_golden_image
+    const GOLDEN_HASH: u64 = 0x99bb_2337_ccc6_0249;
+
+    // Image de référence du dessin procédural : sol, un obstacle de chaque
+    // sorte et le joueur au repos. Un changement voulu du dessin se vérifie
+    // sur l'image écrite dans le dossier temporaire, puis l'empreinte est
+    // mise à jour
+    #[test]
+    fn procedural_art_matches_golden_image() {
+        let mut renderer = SoftwareRenderer::new(320, 160).unwrap();
+        renderer.clear_background(Color::new(135, 206, 235, 255));
+
+        let ground = SceneryElement::new(0.0, 130.0, 320.0, 30.0, Color::DARKGREEN, LAYER_GAMEPLAY);
+        ground.draw(&mut renderer, 0.0, 130.0, 320.0);
+        for (x, y, obstacle_type, size) in [
+            (40.0, 100.0, ObstacleType::Spike, Vector2::new(30.0, 30.0)),
+            (100.0, 90.0, ObstacleType::Block, Vector2::new(40.0, 40.0)),
+            (
+                160.0,
+                60.0,
+                ObstacleType::FloatingPlatform,
+                Vector2::new(100.0, 20.0),
+            ),
+        ] {
+            let obstacle = Obstacle::new(Vector2::new(x, y), obstacle_type, size);
+            obstacle.draw(&mut renderer, x, y, 320.0);
+        }
+        let player = Player::new(Vector2::new(290.0, 70.0), Vector2::new(40.0, 60.0));
+        player.draw(&mut renderer, 290.0, 70.0, 320.0);
+
+        let hash = pixel_hash(&renderer);
+        if hash != GOLDEN_HASH {
+            let path = std::env::temp_dir().join("vibegame-golden.png");
+            renderer.save_png(&path).unwrap();
+            panic!(
+                "image différente de la référence ({:#x}), voir {}",
+                hash,
+                path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn new_rejects_sizes_that_overflow() {
+        assert!(SoftwareRenderer::new(u32::MAX, u32::MAX).is_err());
+        assert!(SoftwareRenderer::new(0, 0).is_ok());
+    }
+
+    #[test]
+    fn png_round_trip_keeps_pixels() {
+        let mut renderer = SoftwareRenderer::new(64, 48).unwrap();
+        renderer.clear_background(Color::new(20, 30, 40, 255));
+        renderer.draw_rectangle(5, 5, 20, 10, Color::RED);
+        renderer.draw_circle(40, 30, 8.0, Color::new(255, 255, 255, 128));
+        renderer.draw_line(0, 47, 63, 0, Color::GREEN);
+
+        let path = std::env::temp_dir().join(format!(
+            "vibegame-software-renderer-{}.png",
+            std::process::id()
+        ));
+        renderer.save_png(&path).unwrap();
+        let loaded = SoftwareRenderer::load_png(&path);
+        let _ = std::fs::remove_file(&path);
+        let loaded = loaded.unwrap();
+
+        assert_eq!(loaded.get_width(), 64);
+        assert_eq!(loaded.get_height(), 48);
+        assert_eq!(loaded.get_pixels(), renderer.get_pixels());
+        assert_eq!(loaded.get_pixel(10, 10), Some(Color::RED));
+    }
+}
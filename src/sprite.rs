@@ -0,0 +1,297 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::animation::frame_at;
+use crate::color::Color;
+
+// Dossier des ressources graphiques chargé au démarrage s'il existe
+pub const DEFAULT_ASSETS_DIR: &str = "assets";
+
+// Description des feuilles de sprites, dans le dossier des ressources
+pub const SPRITE_MANIFEST: &str = "sprites.toml";
+
+// Entrée du fichier de description : une feuille par entité, découpée en
+// images de même taille, numérotées ligne par ligne depuis le coin haut gauche
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SheetEntry {
+    image: PathBuf, // Relatif au dossier des ressources
+    frame_width: u32,
+    frame_height: u32,
+    #[serde(default)]
+    animations: BTreeMap<String, AnimationEntry>, // Par nom (voir AnimationState::get_name)
+}
+
+// Animation d'une feuille : images jouées dans l'ordre
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AnimationEntry {
+    frames: Vec<usize>,
+    fps: Option<f32>, // Images par seconde ; sinon, rythme de l'animation du jeu
+    #[serde(default)]
+    flip_x: bool, // Images retournées (une course à gauche tirée de la course à droite)
+}
+
+// Animation en cours d'une entité, jouée si la feuille la décrit
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpriteAnimation {
+    pub name: &'static str,
+    pub time: f32,           // Temps écoulé depuis le début de l'animation
+    pub frame_duration: f32, // Durée d'une image si la feuille ne donne pas `fps`
+    pub looping: bool,
+}
+
+// Image à dessiner : feuille (voir PLAYER_SPRITE, ObstacleType::get_sprite_name)
+// et numéro de l'image dans la feuille, remplacé par celui de l'animation
+// quand la feuille la décrit
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpriteFrame {
+    pub sheet: &'static str,
+    pub frame: usize, // Ramené au nombre d'images de la feuille
+    pub animation: Option<SpriteAnimation>,
+    pub flip_x: bool, // Miroir horizontal
+}
+
+// Feuille de sprites décodée en RGBA
+#[derive(Clone, Debug)]
+pub struct SpriteSheet {
+    image_path: PathBuf,
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>, // 4 octets (RGBA) par pixel, ligne par ligne
+    frame_width: u32,
+    frame_height: u32,
+    animations: BTreeMap<String, AnimationEntry>,
+}
+
+impl SpriteSheet {
+    pub fn get_image_path(&self) -> &Path {
+        &self.image_path
+    }
+
+    pub fn get_frame_count(&self) -> usize {
+        ((self.width / self.frame_width) * (self.height / self.frame_height)) as usize
+    }
+
+    // Rectangle (x, y, largeur, hauteur) d'une image dans la feuille ; les
+    // numéros trop grands reviennent au début
+    pub fn get_frame_rect(&self, frame: usize) -> (u32, u32, u32, u32) {
+        let columns = (self.width / self.frame_width) as usize;
+        let frame = frame % self.get_frame_count();
+        (
+            (frame % columns) as u32 * self.frame_width,
+            (frame / columns) as u32 * self.frame_height,
+            self.frame_width,
+            self.frame_height,
+        )
+    }
+
+    // Image de la feuille à dessiner et sens, d'après l'animation en cours
+    // si la feuille la décrit
+    pub fn resolve_frame(&self, sprite: &SpriteFrame) -> (usize, bool) {
+        let entry = sprite
+            .animation
+            .and_then(|animation| Some((animation, self.animations.get(animation.name)?)));
+        match entry {
+            Some((animation, entry)) => {
+                let frame_duration = entry.fps.map_or(animation.frame_duration, |fps| 1.0 / fps);
+                let index = frame_at(
+                    animation.time,
+                    frame_duration,
+                    entry.frames.len(),
+                    animation.looping,
+                );
+                (entry.frames[index], sprite.flip_x != entry.flip_x)
+            }
+            None => (sprite.frame, sprite.flip_x),
+        }
+    }
+
+    pub fn get_pixel(&self, x: u32, y: u32) -> Color {
+        let i = ((y.min(self.height - 1) * self.width + x.min(self.width - 1)) * 4) as usize;
+        Color::new(
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        )
+    }
+}
+
+// Feuilles de sprites disponibles, par nom ; une entité dont la feuille est
+// absente garde son dessin procédural
+#[derive(Clone, Debug, Default)]
+pub struct SpriteAtlas {
+    sheets: BTreeMap<String, SpriteSheet>,
+}
+
+impl SpriteAtlas {
+    pub fn new() -> Self {
+        SpriteAtlas::default()
+    }
+
+    // Charger les feuilles décrites dans `dir/sprites.toml` ; sans ce
+    // fichier, aucune feuille n'est chargée
+    pub fn load(dir: impl AsRef<Path>) -> Result<SpriteAtlas, String> {
+        let dir = dir.as_ref();
+        let manifest_path = dir.join(SPRITE_MANIFEST);
+        let text = match fs::read_to_string(&manifest_path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(SpriteAtlas::new()),
+            Err(error) => {
+                return Err(format!(
+                    "{}: lecture impossible: {}",
+                    manifest_path.display(),
+                    error
+                ))
+            }
+        };
+        let entries: BTreeMap<String, SheetEntry> = toml::from_str(&text)
+            .map_err(|error| format!("{}: fichier invalide: {}", manifest_path.display(), error))?;
+
+        let mut atlas = SpriteAtlas::new();
+        for (name, entry) in entries {
+            let image_path = dir.join(&entry.image);
+            let (width, height, pixels) = load_rgba(&image_path)
+                .map_err(|error| format!("{}: {}", image_path.display(), error))?;
+            if entry.frame_width == 0
+                || entry.frame_height == 0
+                || entry.frame_width > width
+                || entry.frame_height > height
+            {
+                return Err(format!(
+                    "{}: [{}] images de {}x{} impossibles dans une feuille de {}x{}",
+                    manifest_path.display(),
+                    name,
+                    entry.frame_width,
+                    entry.frame_height,
+                    width,
+                    height
+                ));
+            }
+            let sheet = SpriteSheet {
+                image_path,
+                width,
+                height,
+                pixels,
+                frame_width: entry.frame_width,
+                frame_height: entry.frame_height,
+                animations: entry.animations,
+            };
+
+            // Chaque animation doit avoir des images, toutes dans la feuille
+            for (animation_name, animation) in &sheet.animations {
+                let frame_count = sheet.get_frame_count();
+                let invalid = if animation.frames.is_empty() {
+                    Some("aucune image".to_string())
+                } else if let Some(frame) = animation.frames.iter().find(|&&f| f >= frame_count) {
+                    Some(format!(
+                        "image {} absente (la feuille en a {})",
+                        frame, frame_count
+                    ))
+                } else if animation
+                    .fps
+                    .is_some_and(|fps| !(fps.is_finite() && fps > 0.0))
+                {
+                    Some("fps doit être un nombre strictement positif".to_string())
+                } else {
+                    None
+                };
+                if let Some(message) = invalid {
+                    return Err(format!(
+                        "{}: [{}.animations.{}] {}",
+                        manifest_path.display(),
+                        name,
+                        animation_name,
+                        message
+                    ));
+                }
+            }
+            atlas.sheets.insert(name, sheet);
+        }
+        Ok(atlas)
+    }
+
+    pub fn get_sheet(&self, name: &str) -> Option<&SpriteSheet> {
+        self.sheets.get(name)
+    }
+
+    pub fn get_sheets(&self) -> impl Iterator<Item = (&str, &SpriteSheet)> {
+        self.sheets
+            .iter()
+            .map(|(name, sheet)| (name.as_str(), sheet))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sheets.is_empty()
+    }
+}
+
+// Décoder une image PNG quelconque (palette, niveaux de gris, 16 bits...)
+// en RGBA 8 bits
+fn load_rgba(path: &Path) -> io::Result<(u32, u32, Vec<u8>)> {
+    let mut decoder = png::Decoder::new(BufReader::new(File::open(path)?));
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info()?;
+    let buffer_size = reader
+        .output_buffer_size()
+        .ok_or_else(|| io::Error::new(ErrorKind::InvalidData, "image PNG trop grande"))?;
+    let mut buffer = vec![0; buffer_size];
+    let info = reader.next_frame(&mut buffer)?;
+    buffer.truncate(info.buffer_size());
+
+    let pixels = match info.color_type {
+        png::ColorType::Rgba => buffer,
+        png::ColorType::Rgb => buffer
+            .chunks_exact(3)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => buffer
+            .chunks_exact(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        png::ColorType::Grayscale => buffer.iter().flat_map(|&v| [v, v, v, 255]).collect(),
+        png::ColorType::Indexed => {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "palette PNG non convertie",
+            ))
+        }
+    };
+    Ok((info.width, info.height, pixels))
+}
+
+// Feuilles de sprites envoyées à la carte graphique, pour TexturedRenderer
+#[cfg(feature = "raylib")]
+#[derive(Default)]
+pub struct SpriteTextures {
+    atlas: SpriteAtlas,
+    textures: BTreeMap<String, raylib::prelude::Texture2D>,
+}
+
+#[cfg(feature = "raylib")]
+impl SpriteTextures {
+    pub fn load(
+        rl: &mut raylib::prelude::RaylibHandle,
+        thread: &raylib::prelude::RaylibThread,
+        atlas: SpriteAtlas,
+    ) -> Result<SpriteTextures, String> {
+        let mut textures = BTreeMap::new();
+        for (name, sheet) in atlas.get_sheets() {
+            let path = sheet.get_image_path();
+            let texture = rl
+                .load_texture(thread, &path.to_string_lossy())
+                .map_err(|error| format!("{}: {}", path.display(), error))?;
+            textures.insert(name.to_string(), texture);
+        }
+        Ok(SpriteTextures { atlas, textures })
+    }
+
+    pub fn get(&self, name: &str) -> Option<(&SpriteSheet, &raylib::prelude::Texture2D)> {
+        Some((self.atlas.get_sheet(name)?, self.textures.get(name)?))
+    }
+}
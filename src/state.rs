@@ -0,0 +1,571 @@
+use crate::color::Color;
+use crate::events::EventListener;
+use crate::game::{GameState, Input};
+use crate::renderer::{draw_text_centered, Renderer};
+use crate::rng::random_seed;
+use crate::settings::Settings;
+use crate::viewport::Viewport;
+
+const TITLE_TEXT: &str = "Vibe Game";
+const GAME_OVER_TEXT: &str = "Game Over!";
+const RESTART_TEXT: &str = "Appuyez sur ESPACE pour recommencer";
+const PAUSED_TEXT: &str = "Pause";
+const GAME_OVER_FONT_SIZE: i32 = 40;
+const RESTART_FONT_SIZE: i32 = 20;
+const MENU_FONT_SIZE: i32 = 30;
+const PAUSE_DIM_COLOR: Color = Color::new(0, 0, 0, 150);
+
+// Touches d'interface pressées pendant la frame (fronts montants)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MenuInput {
+    pub up: bool,
+    pub down: bool,
+    pub confirm: bool, // Entrée ou Espace
+    pub back: bool,    // Échap
+    pub pause: bool,   // P
+}
+
+impl MenuInput {
+    // Échap ou P mettent la partie en pause (et la reprennent)
+    pub fn requests_pause(&self) -> bool {
+        self.pause || self.back
+    }
+}
+
+// Entrées d'une frame : jeu (maintenues) et interface (pressées)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FrameInput {
+    pub play: Input,
+    pub menu: MenuInput,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StateId {
+    Title,
+    Playing,
+    Paused,
+    GameOver,
+    Settings,
+}
+
+// Changement d'état demandé par l'état courant
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transition {
+    Stay,
+    Switch(StateId),
+    NewRun, // Recommencer une partie puis passer à Playing
+    Quit,
+}
+
+// Un état du jeu gère ses entrées, sa logique et son affichage
+pub trait State {
+    fn get_id(&self) -> StateId;
+    fn update(
+        &mut self,
+        game: &mut GameState,
+        settings: &mut Settings,
+        input: &FrameInput,
+        frame_time: f32,
+    ) -> Transition;
+    // `d` dessine dans la fenêtre ; le monde passe par le `viewport`
+    fn draw(
+        &self,
+        game: &GameState,
+        settings: &Settings,
+        viewport: &Viewport,
+        d: &mut dyn Renderer,
+    );
+}
+
+fn create_state(id: StateId) -> Box<dyn State> {
+    match id {
+        StateId::Title => Box::new(TitleState::new()),
+        StateId::Playing => Box::new(PlayingState),
+        StateId::Paused => Box::new(PausedState::new()),
+        StateId::GameOver => Box::new(GameOverState),
+        StateId::Settings => Box::new(SettingsState::new()),
+    }
+}
+
+// Machine à états : possède la partie, les préférences et l'état courant
+pub struct StateMachine {
+    state: Box<dyn State>,
+    game: GameState,
+    settings: Settings,
+    launch_seed: Option<u64>, // Graine fixée au lancement, réutilisée à chaque partie
+    listeners: Vec<Box<dyn EventListener>>, // Abonnés aux événements de la partie
+}
+
+impl StateMachine {
+    pub fn new(
+        game: GameState,
+        settings: Settings,
+        launch_seed: Option<u64>,
+        initial: StateId,
+    ) -> Self {
+        let mut machine = StateMachine {
+            state: create_state(initial),
+            game,
+            settings,
+            launch_seed,
+            listeners: Vec::new(),
+        };
+        machine.apply_settings();
+        machine
+    }
+
+    // Mettre à jour l'état courant et appliquer la transition demandée
+    pub fn update(&mut self, input: &FrameInput, frame_time: f32) -> Transition {
+        let transition = self
+            .state
+            .update(&mut self.game, &mut self.settings, input, frame_time);
+
+        // Distribuer les événements de la frame avant une éventuelle remise à zéro
+        self.game.get_events_mut().dispatch(&mut self.listeners);
+
+        match transition {
+            Transition::Stay | Transition::Quit => {}
+            Transition::Switch(id) => self.state = create_state(id),
+            Transition::NewRun => {
+                let seed = self.launch_seed.unwrap_or_else(random_seed);
+                self.game.reset(seed);
+                self.state = create_state(StateId::Playing);
+            }
+        }
+
+        self.apply_settings();
+        transition
+    }
+
+    // Abonner un écouteur (son, statistiques, succès...) aux événements
+    pub fn add_listener(&mut self, listener: Box<dyn EventListener>) {
+        self.listeners.push(listener);
+    }
+
+    // Transmettre à la partie les préférences qui la concernent
+    fn apply_settings(&mut self) {
+        self.game.set_screen_shake(self.settings.screen_shake);
+        self.game
+            .set_transparent_foreground(self.settings.transparent_foreground);
+    }
+
+    pub fn draw(&self, viewport: &Viewport, d: &mut dyn Renderer) {
+        self.state.draw(&self.game, &self.settings, viewport, d);
+    }
+
+    pub fn get_state_id(&self) -> StateId {
+        self.state.get_id()
+    }
+
+    pub fn get_game_mut(&mut self) -> &mut GameState {
+        &mut self.game
+    }
+
+    pub fn get_game(&self) -> &GameState {
+        &self.game
+    }
+
+    pub fn get_settings(&self) -> &Settings {
+        &self.settings
+    }
+}
+
+// Monde mis à l'échelle de la fenêtre, avec les bandes noires éventuelles
+fn draw_world(game: &GameState, viewport: &Viewport, d: &mut dyn Renderer) {
+    game.draw(&mut viewport.scaled(d));
+    viewport.draw_letterbox(d);
+}
+
+// Centre de la fenêtre, pour placer titres et menus
+fn window_center(viewport: &Viewport) -> (i32, i32) {
+    (
+        viewport.get_window_width() as i32 / 2,
+        viewport.get_window_height() as i32 / 2,
+    )
+}
+
+// Score et barre de charge du saut, affichés pendant la partie (ancrés en
+// haut à gauche de la fenêtre)
+fn draw_hud(game: &GameState, d: &mut dyn Renderer) {
+    d.draw_text(
+        &format!("Score: {}", game.get_score()),
+        10,
+        30,
+        20,
+        Color::WHITE,
+    );
+
+    // Afficher la barre de charge du saut
+    game.get_render_player()
+        .draw_jump_charge(d, game.get_jump_charge_time_max());
+}
+
+// Liste d'options verticale centrée sur `center_x`, l'option sélectionnée est
+// mise en évidence
+fn draw_menu(d: &mut dyn Renderer, items: &[String], selected: usize, center_x: i32, y: i32) {
+    let width = items
+        .iter()
+        .map(|item| d.measure_text(&format!("> {}", item), MENU_FONT_SIZE))
+        .max()
+        .unwrap_or(0);
+    let x = center_x - width / 2;
+
+    for (index, item) in items.iter().enumerate() {
+        let (prefix, color) = if index == selected {
+            ("> ", Color::YELLOW)
+        } else {
+            ("  ", Color::WHITE)
+        };
+        d.draw_text(
+            &format!("{}{}", prefix, item),
+            x,
+            y + index as i32 * (MENU_FONT_SIZE + 10),
+            MENU_FONT_SIZE,
+            color,
+        );
+    }
+}
+
+// Déplacer la sélection d'un menu de `count` options
+fn move_selection(selected: usize, count: usize, menu: &MenuInput) -> usize {
+    if menu.up {
+        (selected + count - 1) % count
+    } else if menu.down {
+        (selected + 1) % count
+    } else {
+        selected
+    }
+}
+
+// Écran titre : jouer, paramètres, quitter
+pub struct TitleState {
+    selected: usize,
+}
+
+impl TitleState {
+    const ITEMS: [&'static str; 3] = ["Jouer", "Paramètres", "Quitter"];
+
+    pub fn new() -> Self {
+        TitleState { selected: 0 }
+    }
+}
+
+impl Default for TitleState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl State for TitleState {
+    fn get_id(&self) -> StateId {
+        StateId::Title
+    }
+
+    fn update(
+        &mut self,
+        _: &mut GameState,
+        _: &mut Settings,
+        input: &FrameInput,
+        _: f32,
+    ) -> Transition {
+        self.selected = move_selection(self.selected, Self::ITEMS.len(), &input.menu);
+
+        if input.menu.back {
+            return Transition::Quit;
+        }
+        if !input.menu.confirm {
+            return Transition::Stay;
+        }
+        match self.selected {
+            0 => Transition::NewRun,
+            1 => Transition::Switch(StateId::Settings),
+            _ => Transition::Quit,
+        }
+    }
+
+    fn draw(&self, game: &GameState, _: &Settings, viewport: &Viewport, d: &mut dyn Renderer) {
+        draw_world(game, viewport, d);
+
+        let (center_x, center_y) = window_center(viewport);
+        draw_text_centered(
+            d,
+            TITLE_TEXT,
+            center_x,
+            center_y - 150,
+            GAME_OVER_FONT_SIZE,
+            Color::WHITE,
+        );
+
+        let items: Vec<String> = Self::ITEMS.iter().map(|item| item.to_string()).collect();
+        draw_menu(d, &items, self.selected, center_x, center_y - 60);
+    }
+}
+
+// Partie en cours
+pub struct PlayingState;
+
+impl State for PlayingState {
+    fn get_id(&self) -> StateId {
+        StateId::Playing
+    }
+
+    fn update(
+        &mut self,
+        game: &mut GameState,
+        _: &mut Settings,
+        input: &FrameInput,
+        frame_time: f32,
+    ) -> Transition {
+        if input.menu.requests_pause() {
+            return Transition::Switch(StateId::Paused);
+        }
+
+        game.update(input.play, frame_time);
+
+        // L'écran de fin attend la fin de l'arrêt sur image du choc
+        if game.is_game_over() && !game.is_hit_stopped() {
+            Transition::Switch(StateId::GameOver)
+        } else {
+            Transition::Stay
+        }
+    }
+
+    fn draw(&self, game: &GameState, _: &Settings, viewport: &Viewport, d: &mut dyn Renderer) {
+        draw_world(game, viewport, d);
+        draw_hud(game, d);
+    }
+}
+
+// Partie suspendue : la simulation n'avance plus, le menu permet de
+// reprendre, recommencer ou revenir au titre
+pub struct PausedState {
+    selected: usize,
+}
+
+impl PausedState {
+    const ITEMS: [&'static str; 3] = ["Reprendre", "Recommencer", "Retour au titre"];
+
+    pub fn new() -> Self {
+        PausedState { selected: 0 }
+    }
+}
+
+impl Default for PausedState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl State for PausedState {
+    fn get_id(&self) -> StateId {
+        StateId::Paused
+    }
+
+    fn update(
+        &mut self,
+        _: &mut GameState,
+        _: &mut Settings,
+        input: &FrameInput,
+        _: f32,
+    ) -> Transition {
+        self.selected = move_selection(self.selected, Self::ITEMS.len(), &input.menu);
+
+        if input.menu.requests_pause() {
+            return Transition::Switch(StateId::Playing);
+        }
+        if !input.menu.confirm {
+            return Transition::Stay;
+        }
+        match self.selected {
+            0 => Transition::Switch(StateId::Playing),
+            // Même remise à zéro que depuis l'écran de fin de partie
+            1 => Transition::NewRun,
+            _ => Transition::Switch(StateId::Title),
+        }
+    }
+
+    fn draw(&self, game: &GameState, _: &Settings, viewport: &Viewport, d: &mut dyn Renderer) {
+        draw_world(game, viewport, d);
+        draw_hud(game, d);
+
+        // Assombrir la scène figée
+        d.draw_rectangle(
+            0,
+            0,
+            viewport.get_window_width() as i32,
+            viewport.get_window_height() as i32,
+            PAUSE_DIM_COLOR,
+        );
+
+        let (center_x, center_y) = window_center(viewport);
+        draw_text_centered(
+            d,
+            PAUSED_TEXT,
+            center_x,
+            center_y - 150,
+            GAME_OVER_FONT_SIZE,
+            Color::WHITE,
+        );
+
+        let items: Vec<String> = Self::ITEMS.iter().map(|item| item.to_string()).collect();
+        draw_menu(d, &items, self.selected, center_x, center_y - 60);
+    }
+}
+
+// Fin de partie : recommencer ou revenir au titre
+pub struct GameOverState;
+
+impl State for GameOverState {
+    fn get_id(&self) -> StateId {
+        StateId::GameOver
+    }
+
+    fn update(
+        &mut self,
+        game: &mut GameState,
+        _: &mut Settings,
+        input: &FrameInput,
+        frame_time: f32,
+    ) -> Transition {
+        // Laisser le tremblement du choc retomber
+        game.update_effects(frame_time);
+
+        if input.menu.confirm {
+            Transition::NewRun
+        } else if input.menu.back {
+            Transition::Switch(StateId::Title)
+        } else {
+            Transition::Stay
+        }
+    }
+
+    fn draw(&self, game: &GameState, _: &Settings, viewport: &Viewport, d: &mut dyn Renderer) {
+        draw_world(game, viewport, d);
+        draw_hud(game, d);
+
+        let (center_x, center_y) = window_center(viewport);
+        draw_text_centered(
+            d,
+            GAME_OVER_TEXT,
+            center_x,
+            center_y - GAME_OVER_FONT_SIZE / 2,
+            GAME_OVER_FONT_SIZE,
+            Color::RED,
+        );
+
+        draw_text_centered(
+            d,
+            RESTART_TEXT,
+            center_x,
+            center_y + 50,
+            RESTART_FONT_SIZE,
+            Color::WHITE,
+        );
+
+        draw_text_centered(
+            d,
+            &format!("Graine: {}", game.get_seed()),
+            center_x,
+            center_y + 80,
+            RESTART_FONT_SIZE,
+            Color::WHITE,
+        );
+    }
+}
+
+// Écran des paramètres : chaque option se bascule avec Entrée
+pub struct SettingsState {
+    selected: usize,
+}
+
+impl SettingsState {
+    const ITEM_COUNT: usize = 4;
+
+    pub fn new() -> Self {
+        SettingsState { selected: 0 }
+    }
+
+    fn items(settings: &Settings) -> Vec<String> {
+        let on_off = |value: bool| if value { "Oui" } else { "Non" };
+        vec![
+            format!("Afficher les FPS : {}", on_off(settings.show_fps)),
+            format!("Tremblement de l'écran : {}", on_off(settings.screen_shake)),
+            format!(
+                "Avant-plan transparent : {}",
+                on_off(settings.transparent_foreground)
+            ),
+            "Retour".to_string(),
+        ]
+    }
+}
+
+impl Default for SettingsState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl State for SettingsState {
+    fn get_id(&self) -> StateId {
+        StateId::Settings
+    }
+
+    fn update(
+        &mut self,
+        _: &mut GameState,
+        settings: &mut Settings,
+        input: &FrameInput,
+        _: f32,
+    ) -> Transition {
+        self.selected = move_selection(self.selected, Self::ITEM_COUNT, &input.menu);
+
+        if input.menu.back {
+            return Transition::Switch(StateId::Title);
+        }
+        if !input.menu.confirm {
+            return Transition::Stay;
+        }
+        match self.selected {
+            0 => {
+                settings.show_fps = !settings.show_fps;
+                Transition::Stay
+            }
+            1 => {
+                settings.screen_shake = !settings.screen_shake;
+                Transition::Stay
+            }
+            2 => {
+                settings.transparent_foreground = !settings.transparent_foreground;
+                Transition::Stay
+            }
+            _ => Transition::Switch(StateId::Title),
+        }
+    }
+
+    fn draw(
+        &self,
+        game: &GameState,
+        settings: &Settings,
+        viewport: &Viewport,
+        d: &mut dyn Renderer,
+    ) {
+        draw_world(game, viewport, d);
+
+        let (center_x, center_y) = window_center(viewport);
+        draw_text_centered(
+            d,
+            "Paramètres",
+            center_x,
+            center_y - 150,
+            GAME_OVER_FONT_SIZE,
+            Color::WHITE,
+        );
+        draw_menu(
+            d,
+            &Self::items(settings),
+            self.selected,
+            center_x,
+            center_y - 60,
+        );
+    }
+}
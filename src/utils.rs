@@ -1,9 +1,24 @@
-use rand::{thread_rng, Rng};
+use rand::Rng;
+#[cfg(feature = "raylib")]
 use raylib::{ffi::KeyboardKey, RaylibHandle};
 
-use crate::scenery::{create_scenery_element, Scenery, SceneryType};
-
-pub fn handle_input(rl: &RaylibHandle) -> (f32, bool) {
+use crate::biome::{palette_at, pick_biome};
+#[cfg(feature = "raylib")]
+use crate::game::Input;
+use crate::drawable::{
+    Drawable, LAYER_BACKGROUND, LAYER_FOREGROUND, LAYER_GAMEPLAY, LAYER_MIDDLE,
+};
+use crate::rng::create_chunk_rng;
+use crate::scenery::{create_scenery_element, parallax_factor, SceneryType, SCENERY_CHUNK_WIDTH};
+#[cfg(feature = "raylib")]
+use crate::state::MenuInput;
+
+// Bandes de sol par tronçon de décor, pour des transitions de biome sans
+// changement brusque de couleur
+const GROUND_STRIPS_PER_CHUNK: usize = 8;
+
+#[cfg(feature = "raylib")]
+pub fn handle_input(rl: &RaylibHandle) -> Input {
     // Direction du mouvement (gauche/droite)
     let mut direction = 0.0;
     
@@ -17,88 +32,174 @@ pub fn handle_input(rl: &RaylibHandle) -> (f32, bool) {
     // État de la touche espace (pour le saut)
     let space_pressed = rl.is_key_down(KeyboardKey::KEY_SPACE);
     
-    (direction, space_pressed)
+    Input::new(direction, space_pressed)
 }
 
-// Ajoutez cette fonction pour générer le monde
-pub fn generate_world(ground_level: f32, _screen_width: f32) -> Scenery {
-    let mut rng = thread_rng();
-    let mut scenery = Scenery::new();
-
-    // Ajouter des arbres
-    for i in 0..20 {
-        let x = i as f32 * 300.0 + rng.random_range(0..100) as f32;
-        let height = 100.0 + rng.random_range(50..150) as f32;
-
-        let tree_elements = create_scenery_element(
-            SceneryType::Tree,
-            x,
-            0.0, // Y sera calculé en fonction de ground_level
-            Some((height, 0.0)),
-            ground_level,
-        );
-
-        scenery.extend(tree_elements);
-    }
-
-    // Ajouter des nuages
-    for i in 0..10 {
-        let x = i as f32 * 400.0 + rng.random_range(0..200) as f32;
-        let y = rng.random_range(50..150) as f32;
-        let width = 100.0 + rng.random_range(0..50) as f32;
-
-        let cloud_element =
-            create_scenery_element(SceneryType::Cloud, x, y, Some((width, 40.0)), ground_level);
-
-        scenery.extend(cloud_element);
-    }
-
-    // Ajouter des montagnes en arrière-plan
-    for i in 0..5 {
-        let x = i as f32 * 500.0;
-        let height = 200.0 + rng.random_range(0..100) as f32;
-
-        let mountain_element = create_scenery_element(
-            SceneryType::Mountain,
-            x,
-            0.0, // Y sera calculé en fonction de ground_level
-            Some((height, 0.0)),
-            ground_level,
-        );
-
-        scenery.extend(mountain_element);
+#[cfg(feature = "raylib")]
+pub fn handle_menu_input(rl: &RaylibHandle) -> MenuInput {
+    // Touches d'interface pressées pendant cette frame
+    MenuInput {
+        up: rl.is_key_pressed(KeyboardKey::KEY_UP),
+        down: rl.is_key_pressed(KeyboardKey::KEY_DOWN),
+        confirm: rl.is_key_pressed(KeyboardKey::KEY_ENTER)
+            || rl.is_key_pressed(KeyboardKey::KEY_SPACE),
+        back: rl.is_key_pressed(KeyboardKey::KEY_ESCAPE),
+        pause: rl.is_key_pressed(KeyboardKey::KEY_P),
     }
+}
 
-    // Ajouter du sol/herbe
-    for i in 0..30 {
-        let x = i as f32 * 200.0;
-
-        // Sol principal
-        let ground_element = create_scenery_element(
-            SceneryType::Ground,
-            x,
-            0.0, // Y sera calculé en fonction de ground_level
-            None,
-            ground_level,
-        );
-
-        scenery.extend(ground_element);
-
-        // Petites touffes d'herbe aléatoires
-        if rng.random_bool(0.6) {
-            let grass_x = x + rng.random_range(20..180) as f32;
-
-            let grass_element = create_scenery_element(
-                SceneryType::GrassTuft,
-                grass_x,
-                0.0, // Y sera calculé en fonction de ground_level
-                None,
-                ground_level,
-            );
-
-            scenery.extend(grass_element);
+// Générer le décor d'une couche pour un tronçon du monde ; le contenu ne
+// dépend que de la graine, de la couche et de l'index du tronçon, si bien
+// qu'un tronçon supprimé puis recréé est identique. Tous les éléments
+// commencent dans le tronçon. Les éléments et leurs couleurs viennent du
+// biome traversé (mélange de deux biomes dans les transitions).
+pub fn generate_scenery_chunk(
+    layer: i32,
+    chunk: i64,
+    seed: u64,
+    ground_level: f32,
+    screen_height: f32,
+) -> Vec<Box<dyn Drawable>> {
+    let mut rng = create_chunk_rng(seed, layer as u64, chunk);
+    let start = chunk as f32 * SCENERY_CHUNK_WIDTH;
+    let half = SCENERY_CHUNK_WIDTH / 2.0;
+    let mut elements = Vec::new();
+
+    // Distance parcourue quand un point de la couche passe au centre de
+    // l'écran : les couches lointaines défilent moins vite, leurs biomes
+    // doivent changer en même temps que celui du sol
+    let distance = |x: f32| x / parallax_factor(layer);
+
+    match layer {
+        // Une montagne par tronçon en arrière-plan, ou deux immeubles
+        LAYER_BACKGROUND => {
+            let biome = pick_biome(distance(start + half), &mut rng);
+
+            if biome.background == SceneryType::Building {
+                for slot in 0..2 {
+                    let width = 100.0 + rng.random_range(0..60) as f32;
+                    let height = 150.0 + rng.random_range(0..150) as f32;
+                    let x = start + slot as f32 * half + rng.random_range(0.0..half - width);
+
+                    elements.extend(create_scenery_element(
+                        SceneryType::Building,
+                        x,
+                        0.0, // Y sera calculé en fonction de ground_level
+                        Some((width, height)),
+                        ground_level,
+                        &biome.palette,
+                    ));
+                }
+            } else {
+                let x = start + rng.random_range(0.0..SCENERY_CHUNK_WIDTH - 300.0);
+                let height = 200.0 + rng.random_range(0..100) as f32;
+
+                elements.extend(create_scenery_element(
+                    biome.background,
+                    x,
+                    0.0, // Y sera calculé en fonction de ground_level
+                    Some((height, 0.0)),
+                    ground_level,
+                    &biome.palette,
+                ));
+            }
+        }
+        // Un nuage, plus ou moins fréquent selon le biome
+        LAYER_MIDDLE => {
+            let biome = pick_biome(distance(start + half), &mut rng);
+
+            if rng.random_bool(biome.cloud_chance) {
+                let width = 100.0 + rng.random_range(0..50) as f32;
+                let x = start + rng.random_range(0.0..SCENERY_CHUNK_WIDTH - width);
+                let y = rng.random_range(50..150) as f32;
+
+                elements.extend(create_scenery_element(
+                    SceneryType::Cloud,
+                    x,
+                    y,
+                    Some((width, 40.0)),
+                    ground_level,
+                    &biome.palette,
+                ));
+            }
         }
+        // Sol, éléments principaux du biome et touffes d'herbe
+        LAYER_GAMEPLAY => {
+            // Sol principal, en bandes dont la couleur suit le mélange des biomes
+            let strip_width = SCENERY_CHUNK_WIDTH / GROUND_STRIPS_PER_CHUNK as f32;
+            for strip in 0..GROUND_STRIPS_PER_CHUNK {
+                let x = start + strip as f32 * strip_width;
+
+                elements.extend(create_scenery_element(
+                    SceneryType::Ground,
+                    x,
+                    0.0, // Y sera calculé en fonction de ground_level
+                    Some((strip_width, 50.0)),
+                    ground_level,
+                    &palette_at(x + strip_width / 2.0),
+                ));
+            }
+
+            // Un élément au plus par moitié de tronçon (le feuillage déborde
+            // de 30 pixels à gauche du tronc et 30 à droite)
+            for slot in 0..2 {
+                let x = start + slot as f32 * half + rng.random_range(30.0..half - 50.0);
+                let biome = pick_biome(distance(x), &mut rng);
+
+                if rng.random_bool(0.85) {
+                    let height = match biome.landmark {
+                        SceneryType::Cactus => 60.0 + rng.random_range(0..60) as f32,
+                        SceneryType::StreetLamp => 120.0 + rng.random_range(0..50) as f32,
+                        _ => 100.0 + rng.random_range(50..150) as f32,
+                    };
+
+                    elements.extend(create_scenery_element(
+                        biome.landmark,
+                        x,
+                        0.0, // Y sera calculé en fonction de ground_level
+                        Some((height, 0.0)),
+                        ground_level,
+                        &biome.palette,
+                    ));
+                }
+            }
+
+            // Petites touffes d'herbe aléatoires
+            let biome = pick_biome(distance(start + half), &mut rng);
+            for _ in 0..rng.random_range(0..=biome.max_grass_tufts) {
+                let grass_x = start + rng.random_range(0.0..SCENERY_CHUNK_WIDTH - 15.0);
+
+                elements.extend(create_scenery_element(
+                    SceneryType::GrassTuft,
+                    grass_x,
+                    0.0, // Y sera calculé en fonction de ground_level
+                    None,
+                    ground_level,
+                    &biome.palette,
+                ));
+            }
+        }
+        // Un élément d'avant-plan dans la plupart des tronçons, posé en bas de l'écran
+        LAYER_FOREGROUND => {
+            let biome = pick_biome(distance(start + half), &mut rng);
+
+            if rng.random_bool(biome.bush_chance) {
+                let width = 80.0 + rng.random_range(0..80) as f32;
+                let height = 30.0 + rng.random_range(0..30) as f32;
+                let x = start + rng.random_range(0.0..SCENERY_CHUNK_WIDTH - width);
+
+                elements.extend(create_scenery_element(
+                    SceneryType::Bush,
+                    x,
+                    screen_height,
+                    Some((width, height)),
+                    ground_level,
+                    &biome.palette,
+                ));
+            }
+        }
+        _ => {}
     }
 
-    scenery
-}
\ No newline at end of file
+    elements
+}
@@ -0,0 +1,190 @@
+use crate::color::Color;
+use crate::math::Vector2;
+use crate::renderer::Renderer;
+use crate::sprite::SpriteFrame;
+
+// Rapports largeur/hauteur extrêmes de la zone de jeu ; au-delà, des bandes
+// noires comblent le reste de la fenêtre
+const MIN_ASPECT_RATIO: f32 = 4.0 / 3.0;
+const MAX_ASPECT_RATIO: f32 = 21.0 / 9.0;
+
+const LETTERBOX_COLOR: Color = Color::BLACK;
+
+// Correspondance entre la fenêtre et la résolution logique du monde : la
+// hauteur logique est fixe (celle de la configuration), la largeur logique
+// suit les proportions de la fenêtre dans la limite des rapports autorisés
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Viewport {
+    window_width: f32,
+    window_height: f32,
+    logical_width: f32,
+    logical_height: f32,
+    scale: f32,    // Pixels de fenêtre par pixel logique
+    offset_x: f32, // Largeur des bandes noires à gauche et à droite
+    offset_y: f32, // Hauteur des bandes noires en haut et en bas
+}
+
+impl Viewport {
+    pub fn new(window_width: f32, window_height: f32, logical_height: f32) -> Self {
+        let window_width = window_width.max(1.0);
+        let window_height = window_height.max(1.0);
+        let aspect_ratio = (window_width / window_height).clamp(MIN_ASPECT_RATIO, MAX_ASPECT_RATIO);
+        let logical_width = logical_height * aspect_ratio;
+
+        // Remplir la fenêtre au maximum sans déformer la zone de jeu
+        let scale = (window_width / logical_width).min(window_height / logical_height);
+
+        Viewport {
+            window_width,
+            window_height,
+            logical_width,
+            logical_height,
+            scale,
+            offset_x: (window_width - logical_width * scale) / 2.0,
+            offset_y: (window_height - logical_height * scale) / 2.0,
+        }
+    }
+
+    // Largeur logique la plus étroite, celle d'une fenêtre en 4:3 ou plus haute
+    pub fn min_logical_width(logical_height: f32) -> f32 {
+        logical_height * MIN_ASPECT_RATIO
+    }
+
+    // Renderer qui dessine en coordonnées logiques dans la fenêtre
+    pub fn scaled<'a>(&self, target: &'a mut dyn Renderer) -> ScaledRenderer<'a> {
+        ScaledRenderer {
+            target,
+            scale: self.scale,
+            offset_x: self.offset_x,
+            offset_y: self.offset_y,
+        }
+    }
+
+    // Masquer ce qui dépasse de la zone de jeu
+    pub fn draw_letterbox(&self, d: &mut dyn Renderer) {
+        let window_width = self.window_width.ceil() as i32;
+        let window_height = self.window_height.ceil() as i32;
+        let side = self.offset_x.ceil() as i32;
+        let top = self.offset_y.ceil() as i32;
+
+        if side > 0 {
+            d.draw_rectangle(0, 0, side, window_height, LETTERBOX_COLOR);
+            d.draw_rectangle(window_width - side, 0, side, window_height, LETTERBOX_COLOR);
+        }
+        if top > 0 {
+            d.draw_rectangle(0, 0, window_width, top, LETTERBOX_COLOR);
+            d.draw_rectangle(0, window_height - top, window_width, top, LETTERBOX_COLOR);
+        }
+    }
+
+    pub fn get_window_width(&self) -> f32 {
+        self.window_width
+    }
+
+    pub fn get_window_height(&self) -> f32 {
+        self.window_height
+    }
+
+    pub fn get_logical_width(&self) -> f32 {
+        self.logical_width
+    }
+
+    pub fn get_logical_height(&self) -> f32 {
+        self.logical_height
+    }
+
+    pub fn get_scale(&self) -> f32 {
+        self.scale
+    }
+}
+
+// Applique la mise à l'échelle du Viewport à chaque appel de dessin
+pub struct ScaledRenderer<'a> {
+    target: &'a mut dyn Renderer,
+    scale: f32,
+    offset_x: f32,
+    offset_y: f32,
+}
+
+impl ScaledRenderer<'_> {
+    fn x(&self, x: f32) -> i32 {
+        (self.offset_x + x * self.scale).round() as i32
+    }
+
+    fn y(&self, y: f32) -> i32 {
+        (self.offset_y + y * self.scale).round() as i32
+    }
+
+    fn point(&self, point: Vector2) -> Vector2 {
+        Vector2::new(
+            self.offset_x + point.x * self.scale,
+            self.offset_y + point.y * self.scale,
+        )
+    }
+
+    // Convertir les deux bords plutôt que la taille évite les trous d'un
+    // pixel entre rectangles voisins
+    fn rectangle(&self, x: i32, y: i32, width: i32, height: i32) -> (i32, i32, i32, i32) {
+        let left = self.x(x as f32);
+        let top = self.y(y as f32);
+        let right = self.x((x + width) as f32);
+        let bottom = self.y((y + height) as f32);
+        (left, top, right - left, bottom - top)
+    }
+}
+
+impl Renderer for ScaledRenderer<'_> {
+    fn clear_background(&mut self, color: Color) {
+        self.target.clear_background(color);
+    }
+
+    fn draw_rectangle(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        let (x, y, width, height) = self.rectangle(x, y, width, height);
+        self.target.draw_rectangle(x, y, width, height, color);
+    }
+
+    fn draw_rectangle_lines(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        let (x, y, width, height) = self.rectangle(x, y, width, height);
+        self.target.draw_rectangle_lines(x, y, width, height, color);
+    }
+
+    fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        let (v1, v2, v3) = (self.point(v1), self.point(v2), self.point(v3));
+        self.target.draw_triangle(v1, v2, v3, color);
+    }
+
+    fn draw_circle(&mut self, center_x: i32, center_y: i32, radius: f32, color: Color) {
+        let (x, y) = (self.x(center_x as f32), self.y(center_y as f32));
+        self.target.draw_circle(x, y, radius * self.scale, color);
+    }
+
+    fn draw_line(&mut self, start_x: i32, start_y: i32, end_x: i32, end_y: i32, color: Color) {
+        let (start_x, start_y) = (self.x(start_x as f32), self.y(start_y as f32));
+        let (end_x, end_y) = (self.x(end_x as f32), self.y(end_y as f32));
+        self.target.draw_line(start_x, start_y, end_x, end_y, color);
+    }
+
+    fn draw_text(&mut self, text: &str, x: i32, y: i32, font_size: i32, color: Color) {
+        let font_size = (font_size as f32 * self.scale).round() as i32;
+        let (x, y) = (self.x(x as f32), self.y(y as f32));
+        self.target.draw_text(text, x, y, font_size, color);
+    }
+
+    fn measure_text(&self, text: &str, font_size: i32) -> i32 {
+        let font_size = (font_size as f32 * self.scale).round() as i32;
+        (self.target.measure_text(text, font_size) as f32 / self.scale).round() as i32
+    }
+
+    fn draw_sprite(
+        &mut self,
+        sprite: SpriteFrame,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        tint: Color,
+    ) -> bool {
+        let (x, y, width, height) = self.rectangle(x, y, width, height);
+        self.target.draw_sprite(sprite, x, y, width, height, tint)
+    }
+}
@@ -0,0 +1,341 @@
+use std::f32::consts::TAU;
+
+use rand::Rng;
+
+use crate::camera::Camera;
+use crate::color::Color;
+use crate::config::WeatherConfig;
+use crate::drawable::{Drawable, LAYER_BACKGROUND, LAYER_FOREGROUND, LAYER_GAMEPLAY, LAYER_MIDDLE};
+use crate::math::Vector2;
+use crate::render_queue::RenderQueue;
+use crate::renderer::Renderer;
+use crate::rng::{create_chunk_rng, GameRng};
+use crate::scenery::parallax_factor;
+
+// Flux aléatoires de la météo (programme des périodes et particules)
+const WEATHER_RNG_STREAM: u64 = 48;
+const PARTICLE_RNG_STREAM: u64 = 49;
+
+// Montée et descente de l'intensité au début et à la fin d'une période (secondes)
+const WEATHER_FADE_TIME: f32 = 4.0;
+
+const MAX_PARTICLES: usize = 800;
+
+// Couches où tombent les particules et part de chacune
+const PARTICLE_LAYERS: [(i32, f32); 4] = [
+    (LAYER_BACKGROUND, 0.3),
+    (LAYER_MIDDLE, 0.3),
+    (LAYER_GAMEPLAY, 0.25),
+    (LAYER_FOREGROUND, 0.15),
+];
+
+// Durée d'un éclair d'orage (secondes)
+const LIGHTNING_DURATION: f32 = 0.25;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeatherType {
+    Clear,
+    Rain,
+    Snow,
+    Storm, // Forte pluie, vent fort et éclairs
+}
+
+impl WeatherType {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            WeatherType::Clear => "Beau temps",
+            WeatherType::Rain => "Pluie",
+            WeatherType::Snow => "Neige",
+            WeatherType::Storm => "Orage",
+        }
+    }
+
+    // Assombrissement dû aux nuages, à pleine intensité
+    fn get_overcast(&self) -> Color {
+        match self {
+            WeatherType::Clear => Color::WHITE,
+            WeatherType::Rain => Color::new(180, 185, 200, 255),
+            WeatherType::Snow => Color::new(215, 220, 230, 255),
+            WeatherType::Storm => Color::new(120, 125, 145, 255),
+        }
+    }
+
+    // Particules créées par seconde à pleine intensité
+    fn get_spawn_rate(&self) -> f32 {
+        match self {
+            WeatherType::Clear => 0.0,
+            WeatherType::Rain => 220.0,
+            WeatherType::Snow => 90.0,
+            WeatherType::Storm => 400.0,
+        }
+    }
+}
+
+// Météo d'une période de la partie, tirée de la graine et de l'index de la
+// période ; la première période est toujours dégagée
+fn weather_for_period(seed: u64, period: i64) -> (WeatherType, f32) {
+    if period == 0 {
+        return (WeatherType::Clear, 0.0);
+    }
+
+    let mut rng = create_chunk_rng(seed, WEATHER_RNG_STREAM, period);
+    let weather_type = match rng.random_range(0..10) {
+        0..=3 => WeatherType::Clear,
+        4..=6 => WeatherType::Rain,
+        7..=8 => WeatherType::Snow,
+        _ => WeatherType::Storm,
+    };
+
+    // Vent (pixels/s²), vers la gauche ou vers la droite
+    let strength = match weather_type {
+        WeatherType::Clear => 0.0,
+        WeatherType::Rain => rng.random_range(0.0..40.0),
+        WeatherType::Snow => rng.random_range(0.0..30.0),
+        WeatherType::Storm => rng.random_range(80.0..160.0),
+    };
+    let direction = if rng.random_bool(0.5) { 1.0 } else { -1.0 };
+    (weather_type, strength * direction)
+}
+
+// Goutte de pluie ou flocon, dans les coordonnées de sa couche de parallaxe
+#[derive(Clone, Copy, Debug)]
+pub struct WeatherParticle {
+    position: Vector2,
+    velocity: Vector2,
+    layer: i32,
+    size: f32,
+    phase: f32, // Décalage du balancement des flocons
+    is_snow: bool,
+}
+
+impl Drawable for WeatherParticle {
+    fn draw(&self, d: &mut dyn Renderer, screen_x: f32, screen_y: f32, _screen_width: f32) {
+        // Les couches lointaines sont plus pâles
+        let alpha = (255.0 * (0.3 + 0.5 * parallax_factor(self.layer))).min(255.0) as u8;
+
+        if self.is_snow {
+            let size = self.size.max(1.0) as i32;
+            d.draw_rectangle(
+                screen_x as i32,
+                screen_y as i32,
+                size,
+                size,
+                Color::new(255, 255, 255, alpha),
+            );
+        } else {
+            // Trait dans la direction de la chute
+            let tail_x = screen_x - self.velocity.x * 0.02 * self.size;
+            let tail_y = screen_y - self.velocity.y * 0.02 * self.size;
+            d.draw_line(
+                screen_x as i32,
+                screen_y as i32,
+                tail_x as i32,
+                tail_y as i32,
+                Color::new(170, 190, 230, alpha),
+            );
+        }
+    }
+
+    fn get_position(&self) -> (f32, f32) {
+        (self.position.x, self.position.y)
+    }
+
+    fn get_size(&self) -> (f32, f32) {
+        (self.size, self.size)
+    }
+
+    fn get_layer(&self) -> i32 {
+        self.layer
+    }
+
+    // Devant le joueur dans sa couche
+    fn get_depth(&self) -> f32 {
+        3.0
+    }
+}
+
+// Météo de la partie : change à chaque période de `change_interval`
+// secondes, fait tomber pluie ou neige sur toutes les couches et souffle un
+// vent qui dévie les sauts ; tout est tiré de la graine, une même partie
+// rejouée a la même météo
+pub struct Weather {
+    seed: u64,
+    config: WeatherConfig,
+    rng: GameRng, // Placement des particules
+    particles: Vec<WeatherParticle>,
+    spawn_budget: f32, // Particules à créer qui n'ont pas encore été créées
+    weather_type: WeatherType,
+    wind: f32,      // Vent de la période (pixels/s²)
+    intensity: f32, // De 0.0 à 1.0, baisse aux changements de période
+    lightning: f32, // Temps restant de l'éclair en cours
+}
+
+impl Weather {
+    pub fn new(seed: u64, config: WeatherConfig) -> Self {
+        Weather {
+            seed,
+            config,
+            rng: create_chunk_rng(seed, PARTICLE_RNG_STREAM, 0),
+            particles: Vec::new(),
+            spawn_budget: 0.0,
+            weather_type: WeatherType::Clear,
+            wind: 0.0,
+            intensity: 0.0,
+            lightning: 0.0,
+        }
+    }
+
+    pub fn set_config(&mut self, config: WeatherConfig) {
+        self.config = config;
+    }
+
+    // Avancer la météo : `run_time` est la durée de la partie, les
+    // particules naissent en haut de la vue et disparaissent en bas
+    pub fn update(&mut self, delta_time: f32, run_time: f32, camera: &Camera, screen_height: f32) {
+        let (weather_type, wind, intensity) = if self.config.enabled {
+            let interval = self.config.change_interval;
+            let period = (run_time / interval).floor() as i64;
+            let (weather_type, wind) = weather_for_period(self.seed, period);
+
+            let elapsed = run_time - period as f32 * interval;
+            let fade = (elapsed / WEATHER_FADE_TIME)
+                .min((interval - elapsed) / WEATHER_FADE_TIME)
+                .clamp(0.0, 1.0);
+            (weather_type, wind, fade)
+        } else {
+            (WeatherType::Clear, 0.0, 0.0)
+        };
+        self.weather_type = weather_type;
+        self.wind = wind;
+        self.intensity = intensity;
+
+        // Éclairs pendant les orages
+        self.lightning = (self.lightning - delta_time).max(0.0);
+        if weather_type == WeatherType::Storm
+            && self.rng.random::<f32>() < 0.2 * intensity * delta_time
+        {
+            self.lightning = LIGHTNING_DURATION;
+        }
+
+        self.move_particles(delta_time, camera, screen_height);
+        self.spawn_particles(delta_time, camera);
+    }
+
+    fn move_particles(&mut self, delta_time: f32, camera: &Camera, screen_height: f32) {
+        let wind = self.wind;
+        for particle in &mut self.particles {
+            particle.velocity.x += (wind * 1.5 - particle.velocity.x) * (delta_time * 2.0).min(1.0);
+            particle.position.x += particle.velocity.x * delta_time;
+            particle.position.y += particle.velocity.y * delta_time;
+
+            // Les flocons se balancent en tombant
+            if particle.is_snow {
+                particle.phase += delta_time;
+                particle.position.x += (particle.phase * TAU * 0.5).sin() * 20.0 * delta_time;
+            }
+        }
+
+        // Oublier ce qui est sorti de la vue par le bas ou par les côtés
+        let view_width = camera.get_view_width();
+        self.particles.retain(|particle| {
+            let factor = parallax_factor(particle.layer);
+            let screen = camera.world_to_screen_parallax(particle.position, factor);
+            screen.y < screen_height && screen.x > -view_width * 0.5 && screen.x < view_width * 1.5
+        });
+    }
+
+    fn spawn_particles(&mut self, delta_time: f32, camera: &Camera) {
+        self.spawn_budget += self.weather_type.get_spawn_rate() * self.intensity * delta_time;
+
+        let is_snow = self.weather_type == WeatherType::Snow;
+        let view_width = camera.get_view_width();
+        while self.spawn_budget >= 1.0 {
+            self.spawn_budget -= 1.0;
+            if self.particles.len() >= MAX_PARTICLES {
+                continue;
+            }
+
+            // Choisir la couche selon sa part
+            let mut roll = self.rng.random::<f32>();
+            let mut layer = LAYER_GAMEPLAY;
+            for (candidate, share) in PARTICLE_LAYERS {
+                if roll < share {
+                    layer = candidate;
+                    break;
+                }
+                roll -= share;
+            }
+            let factor = parallax_factor(layer);
+
+            // Au-dessus de la vue, avec de la marge du côté d'où vient le vent
+            let screen_x = self.rng.random_range(-0.25..1.25) * view_width;
+            let screen_y = -self.rng.random_range(10.0..60.0);
+            let position =
+                camera.screen_to_world_parallax(Vector2::new(screen_x, screen_y), factor);
+
+            // Les couches proches tombent plus vite et sont plus grosses
+            let (fall_speed, size) = if is_snow {
+                (
+                    self.rng.random_range(50.0..90.0),
+                    self.rng.random_range(2.0..4.0),
+                )
+            } else {
+                (
+                    self.rng.random_range(600.0..800.0),
+                    self.rng.random_range(0.8..1.2),
+                )
+            };
+
+            self.particles.push(WeatherParticle {
+                position,
+                velocity: Vector2::new(self.wind * 1.5, fall_speed * factor),
+                layer,
+                size: size * factor,
+                phase: self.rng.random_range(0.0..2.0),
+                is_snow,
+            });
+        }
+    }
+
+    // Ajouter à la file de rendu les particules visibles
+    pub fn queue_draw<'a>(&'a self, queue: &mut RenderQueue<'a>, camera: &Camera) {
+        for particle in &self.particles {
+            let factor = parallax_factor(particle.layer);
+            let screen = camera.world_to_screen_parallax(particle.position, factor);
+            if camera.is_visible(screen.x, particle.size) {
+                queue.push(particle, screen);
+            }
+        }
+    }
+
+    // Vent ressenti par le joueur, nul si désactivé dans la configuration
+    pub fn get_player_wind(&self) -> f32 {
+        if self.config.wind_affects_player {
+            self.wind * self.intensity
+        } else {
+            0.0
+        }
+    }
+
+    // Lumière filtrée par les nuages, à combiner avec celle de l'heure
+    pub fn get_light_filter(&self) -> Color {
+        Color::WHITE.lerp(self.weather_type.get_overcast(), self.intensity)
+    }
+
+    pub fn get_weather_type(&self) -> WeatherType {
+        self.weather_type
+    }
+
+    pub fn get_intensity(&self) -> f32 {
+        self.intensity
+    }
+
+    pub fn get_particle_count(&self) -> usize {
+        self.particles.len()
+    }
+
+    // Éclat de l'éclair en cours, de 0.0 à 1.0
+    pub fn get_lightning(&self) -> f32 {
+        self.lightning / LIGHTNING_DURATION
+    }
+}